@@ -3,7 +3,7 @@ use reqwest::header::{HeaderMap, COOKIE};
 use std::error::Error;
 use std::fs::File;
 use std::io::prelude::*;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 const CACHE_DIR: &str = "input_cache";
 const SESSION_FILE_PATH: &[&str] = &["..", "..", "session.txt"];
@@ -11,7 +11,7 @@ const SESSION_FILE_PATH: &[&str] = &["..", "..", "session.txt"];
 fn get_session_key() -> String {
     let mut path = std::env::current_exe().unwrap();
     path.pop();
-    path.push(&SESSION_FILE_PATH.iter().cloned().collect::<PathBuf>());
+    path.push(SESSION_FILE_PATH.iter().cloned().collect::<PathBuf>());
 
     let mut content = String::new();
     let mut file = File::open(path).unwrap();
@@ -19,8 +19,8 @@ fn get_session_key() -> String {
     content.trim().to_string()
 }
 
-fn get_input_web(day: u8) -> Result<String, Box<dyn Error>> {
-    let url_str = format!("https://adventofcode.com/2020/day/{}/input", day);
+fn get_input_web(year: u16, day: u8) -> Result<String, Box<dyn Error>> {
+    let url_str = format!("https://adventofcode.com/{}/day/{}/input", year, day);
     let mut headers = HeaderMap::new();
     headers.insert(
         COOKIE,
@@ -30,25 +30,29 @@ fn get_input_web(day: u8) -> Result<String, Box<dyn Error>> {
     Ok(resp.text()?)
 }
 
-fn try_get_input(day: u8) -> Result<String, Box<dyn Error>> {
-    let mut path = PathBuf::new();
-    path.push(std::env::current_exe()?.parent().unwrap());
+pub fn cache_path(exe_dir: &Path, year: u16, day: u8) -> PathBuf {
+    let mut path = exe_dir.to_path_buf();
     path.push(CACHE_DIR);
+    path.push(year.to_string());
     path.push(format!("day_{}.txt", day));
+    path
+}
 
-    let _ = std::fs::create_dir(path.parent().unwrap());
+fn try_get_input(year: u16, day: u8) -> Result<String, Box<dyn Error>> {
+    let path = cache_path(std::env::current_exe()?.parent().unwrap(), year, day);
+    let _ = std::fs::create_dir_all(path.parent().unwrap());
 
     if path.exists() {
-        println!("Cache hit for day {}", day);
+        println!("Cache hit for {} day {}", year, day);
         Ok(std::fs::read_to_string(path)?)
     } else {
-        println!("Cache miss for day {}", day);
-        let input_str = get_input_web(day)?;
+        println!("Cache miss for {} day {}", year, day);
+        let input_str = get_input_web(year, day)?;
         std::fs::write(path, &input_str)?;
         Ok(input_str)
     }
 }
 
-pub fn get_input(day: u8) -> String {
-    try_get_input(day).expect("Failed getting input")
+pub fn get_input(year: u16, day: u8) -> String {
+    try_get_input(year, day).expect("Failed getting input")
 }
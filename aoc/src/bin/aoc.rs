@@ -0,0 +1,26 @@
+use aoc::watch::{latest_year, watch, workspace_root};
+
+const USAGE: &str = "Usage: aoc watch <day> [year]";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let (day, year) = match args[..] {
+        ["watch", day] => (day, None),
+        ["watch", day, year] => (day, Some(year)),
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let root = workspace_root().expect("Not inside the workspace");
+    let day: u8 = day.parse().expect("Invalid day");
+    let year: u16 = match year {
+        Some(year) => year.parse().expect("Invalid year"),
+        None => latest_year(&root).expect("No year crates found"),
+    };
+
+    watch(&root, year, day)
+}
@@ -0,0 +1,1488 @@
+pub mod hex;
+mod scalar;
+
+pub use scalar::Scalar;
+
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use std::convert::{From, TryFrom};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum Turn {
+    Front = 0,
+    Right = 1,
+    Back = 2,
+    Left = 3,
+}
+
+impl Mul<Turn> for isize {
+    type Output = Turn;
+
+    fn mul(self, rhs: Turn) -> Self::Output {
+        let n = self.rem_euclid(4) as u8;
+        Turn::try_from((n * u8::from(rhs)) % 4).unwrap()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum Direction {
+    Up = 0,
+    Right = 1,
+    Down = 2,
+    Left = 3,
+}
+
+impl Direction {
+    pub fn turn(self, to: Turn) -> Self {
+        let res = (u8::from(self) + u8::from(to)) % 4;
+        Direction::try_from(res).unwrap()
+    }
+}
+
+// Eight-way directions, numbered clockwise from north in 45° steps
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum Compass {
+    North = 0,
+    NorthEast = 1,
+    East = 2,
+    SouthEast = 3,
+    South = 4,
+    SouthWest = 5,
+    West = 6,
+    NorthWest = 7,
+}
+
+impl Compass {
+    pub const ALL: [Compass; 8] = [
+        Compass::North,
+        Compass::NorthEast,
+        Compass::East,
+        Compass::SouthEast,
+        Compass::South,
+        Compass::SouthWest,
+        Compass::West,
+        Compass::NorthWest,
+    ];
+
+    // Positive steps turn clockwise
+    pub fn turn_45(self, steps: isize) -> Self {
+        let res = (u8::from(self) as isize + steps).rem_euclid(8);
+        Compass::try_from(res as u8).unwrap()
+    }
+
+    pub fn turn(self, to: Turn) -> Self {
+        self.turn_45(2 * u8::from(to) as isize)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        u8::from(self) % 2 == 1
+    }
+}
+
+impl From<Direction> for Compass {
+    fn from(d: Direction) -> Self {
+        Compass::try_from(2 * u8::from(d)).unwrap()
+    }
+}
+
+impl TryFrom<Compass> for Direction {
+    type Error = &'static str;
+
+    fn try_from(c: Compass) -> Result<Self, Self::Error> {
+        if c.is_diagonal() {
+            return Err("Diagonal compass direction");
+        }
+        Ok(Direction::try_from(u8::from(c) / 2).unwrap())
+    }
+}
+
+// Accepts compass letters (NSEW), screen directions (UDLR) and arrows (^v<>)
+impl TryFrom<char> for Compass {
+    type Error = &'static str;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'N' | 'n' | 'U' | 'u' | '^' => Ok(Compass::North),
+            'E' | 'e' | 'R' | 'r' | '>' => Ok(Compass::East),
+            'S' | 's' | 'D' | 'd' | 'v' => Ok(Compass::South),
+            'W' | 'w' | 'L' | 'l' | '<' => Ok(Compass::West),
+            _ => Err("Invalid direction character"),
+        }
+    }
+}
+
+// Like `TryFrom<char>`, plus two-letter diagonals such as "NE" or "sw"
+impl FromStr for Compass {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let first = chars.next().ok_or("Empty direction")?;
+        let second = chars.next();
+        if chars.next().is_some() {
+            return Err("Direction too long");
+        }
+
+        let first = Compass::try_from(first)?;
+        let second = match second {
+            None => return Ok(first),
+            Some(c) => Compass::try_from(c)?,
+        };
+        // Diagonals only combine compass letters, not U/D/L/R or arrows
+        let nsew = s.chars().all(|c| "NSEWnsew".contains(c));
+        match (first, second, nsew) {
+            (Compass::North, Compass::East, true) => Ok(Compass::NorthEast),
+            (Compass::South, Compass::East, true) => Ok(Compass::SouthEast),
+            (Compass::South, Compass::West, true) => Ok(Compass::SouthWest),
+            (Compass::North, Compass::West, true) => Ok(Compass::NorthWest),
+            _ => Err("Invalid diagonal direction"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Coord<T = isize>(pub T, pub T);
+
+impl<T: Scalar> Coord<T> {
+    pub fn origin() -> Coord<T> {
+        Coord(T::ZERO, T::ZERO)
+    }
+
+    pub fn component_min(self, other: Coord<T>) -> Coord<T> {
+        Coord(self.0.min(other.0), self.1.min(other.1))
+    }
+
+    pub fn component_max(self, other: Coord<T>) -> Coord<T> {
+        Coord(self.0.max(other.0), self.1.max(other.1))
+    }
+
+    pub fn checked_add(self, rhs: Delta<T>) -> Option<Coord<T>> {
+        Some(Coord(
+            self.0.checked_add(rhs.0)?,
+            self.1.checked_add(rhs.1)?,
+        ))
+    }
+
+    pub fn checked_sub(self, rhs: Delta<T>) -> Option<Coord<T>> {
+        Some(Coord(
+            self.0.checked_sub(rhs.0)?,
+            self.1.checked_sub(rhs.1)?,
+        ))
+    }
+
+    // Like `other - self`, but None if the delta doesn't fit in `T`
+    pub fn checked_delta_to(self, other: Coord<T>) -> Option<Delta<T>> {
+        Some(Delta(
+            other.0.checked_sub(self.0)?,
+            other.1.checked_sub(self.1)?,
+        ))
+    }
+
+    // Wrapping result, and whether any component overflowed
+    pub fn overflowing_add(self, rhs: Delta<T>) -> (Coord<T>, bool) {
+        let (x, ox) = self.0.overflowing_add(rhs.0);
+        let (y, oy) = self.1.overflowing_add(rhs.1);
+        (Coord(x, y), ox || oy)
+    }
+
+    pub fn overflowing_sub(self, rhs: Delta<T>) -> (Coord<T>, bool) {
+        let (x, ox) = self.0.overflowing_sub(rhs.0);
+        let (y, oy) = self.1.overflowing_sub(rhs.1);
+        (Coord(x, y), ox || oy)
+    }
+
+    // Converts to another scalar type, if both components fit
+    pub fn try_cast<U: Scalar + TryFrom<T>>(self) -> Option<Coord<U>> {
+        Some(Coord(U::try_from(self.0).ok()?, U::try_from(self.1).ok()?))
+    }
+}
+
+impl Coord {
+    // The 4 orthogonally adjacent coordinates
+    pub fn von_neumann(self) -> impl Iterator<Item = Coord> {
+        Delta::VON_NEUMANN.iter().map(move |&d| self + d)
+    }
+
+    // The 8 orthogonally or diagonally adjacent coordinates
+    pub fn moore(self) -> impl Iterator<Item = Coord> {
+        Delta::MOORE.iter().map(move |&d| self + d)
+    }
+
+    // Every coordinate reached by repeatedly stepping by `step`, excluding
+    // `self`. Never ends, so bound it with `take_while` or similar.
+    pub fn ray(self, step: Delta) -> impl Iterator<Item = Coord> {
+        (1isize..).map(move |scale| self + scale * step)
+    }
+
+    // Bresenham line from `self` to `end`, including both endpoints
+    pub fn line_to(self, end: Coord) -> Line {
+        let d = end - self;
+        Line {
+            next: Some(self),
+            end,
+            dx: d.0.abs(),
+            dy: -d.1.abs(),
+            step: d.signum(),
+            err: d.0.abs() - d.1.abs(),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Delta<T = isize>(pub T, pub T);
+
+impl Delta {
+    pub const VON_NEUMANN: [Delta; 4] = [Delta(0, -1), Delta(1, 0), Delta(0, 1), Delta(-1, 0)];
+
+    pub const MOORE: [Delta; 8] = [
+        Delta(-1, -1),
+        Delta(0, -1),
+        Delta(1, -1),
+        Delta(1, 0),
+        Delta(1, 1),
+        Delta(0, 1),
+        Delta(-1, 1),
+        Delta(-1, 0),
+    ];
+}
+
+impl<T: Scalar> Delta<T> {
+    pub fn zero() -> Delta<T> {
+        Delta(T::ZERO, T::ZERO)
+    }
+
+    pub fn turn(&self, t: Turn) -> Delta<T> {
+        match t {
+            Turn::Front => *self,
+            Turn::Right => Delta(-self.1, self.0),
+            Turn::Back => Delta(-self.0, -self.1),
+            Turn::Left => Delta(self.1, -self.0),
+        }
+    }
+
+    pub fn signum(self) -> Delta<T> {
+        Delta(self.0.signum(), self.1.signum())
+    }
+
+    // The smallest step in the same direction that still lands on integer
+    // coordinates, e.g. (4, -6) reduces to (2, -3)
+    pub fn reduced(self) -> Delta<T> {
+        let (mut a, mut b) = (self.0.abs(), self.1.abs());
+        while b != T::ZERO {
+            let t = a % b;
+            a = b;
+            b = t;
+        }
+        if a == T::ZERO {
+            self
+        } else {
+            self / a
+        }
+    }
+
+    pub fn manhattan_len(self) -> T {
+        self.0.abs() + self.1.abs()
+    }
+
+    pub fn chebyshev_len(self) -> T {
+        self.0.abs().max(self.1.abs())
+    }
+
+    pub fn squared_len(self) -> T {
+        self.0 * self.0 + self.1 * self.1
+    }
+
+    pub fn hex_len(self) -> T {
+        (self.0.abs() + self.1.abs() + (self.0 + self.1).abs()) / (T::ONE + T::ONE)
+    }
+
+    pub fn component_min(self, other: Delta<T>) -> Delta<T> {
+        Delta(self.0.min(other.0), self.1.min(other.1))
+    }
+
+    pub fn component_max(self, other: Delta<T>) -> Delta<T> {
+        Delta(self.0.max(other.0), self.1.max(other.1))
+    }
+
+    pub fn checked_add(self, rhs: Delta<T>) -> Option<Delta<T>> {
+        Some(Delta(
+            self.0.checked_add(rhs.0)?,
+            self.1.checked_add(rhs.1)?,
+        ))
+    }
+
+    pub fn checked_sub(self, rhs: Delta<T>) -> Option<Delta<T>> {
+        Some(Delta(
+            self.0.checked_sub(rhs.0)?,
+            self.1.checked_sub(rhs.1)?,
+        ))
+    }
+
+    pub fn checked_mul(self, rhs: T) -> Option<Delta<T>> {
+        Some(Delta(self.0.checked_mul(rhs)?, self.1.checked_mul(rhs)?))
+    }
+
+    pub fn checked_neg(self) -> Option<Delta<T>> {
+        Some(Delta(self.0.checked_neg()?, self.1.checked_neg()?))
+    }
+
+    pub fn overflowing_add(self, rhs: Delta<T>) -> (Delta<T>, bool) {
+        let (x, ox) = self.0.overflowing_add(rhs.0);
+        let (y, oy) = self.1.overflowing_add(rhs.1);
+        (Delta(x, y), ox || oy)
+    }
+
+    pub fn overflowing_sub(self, rhs: Delta<T>) -> (Delta<T>, bool) {
+        let (x, ox) = self.0.overflowing_sub(rhs.0);
+        let (y, oy) = self.1.overflowing_sub(rhs.1);
+        (Delta(x, y), ox || oy)
+    }
+
+    pub fn overflowing_mul(self, rhs: T) -> (Delta<T>, bool) {
+        let (x, ox) = self.0.overflowing_mul(rhs);
+        let (y, oy) = self.1.overflowing_mul(rhs);
+        (Delta(x, y), ox || oy)
+    }
+
+    pub fn try_cast<U: Scalar + TryFrom<T>>(self) -> Option<Delta<U>> {
+        Some(Delta(U::try_from(self.0).ok()?, U::try_from(self.1).ok()?))
+    }
+}
+
+impl<T: Scalar> Add<Delta<T>> for Coord<T> {
+    type Output = Coord<T>;
+
+    fn add(self, rhs: Delta<T>) -> Self::Output {
+        Coord(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl<T: Scalar> AddAssign<Delta<T>> for Coord<T> {
+    fn add_assign(&mut self, rhs: Delta<T>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Scalar> Sub<Delta<T>> for Coord<T> {
+    type Output = Coord<T>;
+
+    fn sub(self, rhs: Delta<T>) -> Self::Output {
+        Coord(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl<T: Scalar> SubAssign<Delta<T>> for Coord<T> {
+    fn sub_assign(&mut self, rhs: Delta<T>) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Scalar> Sub<Coord<T>> for Coord<T> {
+    type Output = Delta<T>;
+
+    fn sub(self, rhs: Coord<T>) -> Self::Output {
+        Delta(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl<T: Scalar> Add<Delta<T>> for Delta<T> {
+    type Output = Delta<T>;
+
+    fn add(self, rhs: Delta<T>) -> Self::Output {
+        Delta(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl<T: Scalar> AddAssign<Delta<T>> for Delta<T> {
+    fn add_assign(&mut self, rhs: Delta<T>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Scalar> Sub<Delta<T>> for Delta<T> {
+    type Output = Delta<T>;
+
+    fn sub(self, rhs: Delta<T>) -> Self::Output {
+        Delta(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl<T: Scalar> SubAssign<Delta<T>> for Delta<T> {
+    fn sub_assign(&mut self, rhs: Delta<T>) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Scalar> Neg for Delta<T> {
+    type Output = Delta<T>;
+
+    fn neg(self) -> Self::Output {
+        Delta(-self.0, -self.1)
+    }
+}
+
+impl<T: Scalar> Mul<T> for Delta<T> {
+    type Output = Delta<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Delta(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+// `scalar * delta` can't be implemented generically for foreign types
+macro_rules! impl_scalar_mul {
+    ($($t:ty),*) => {
+        $(
+            impl Mul<Delta<$t>> for $t {
+                type Output = Delta<$t>;
+
+                fn mul(self, rhs: Delta<$t>) -> Self::Output {
+                    rhs * self
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_mul!(i8, i16, i32, i64, i128, isize);
+
+// Truncates towards zero, like integer division
+impl<T: Scalar> Div<T> for Delta<T> {
+    type Output = Delta<T>;
+
+    fn div(self, rhs: T) -> Self::Output {
+        Delta(self.0 / rhs, self.1 / rhs)
+    }
+}
+
+impl<T: Scalar> Sum for Delta<T> {
+    fn sum<I: Iterator<Item = Delta<T>>>(iter: I) -> Self {
+        iter.fold(Delta::zero(), |acc, d| acc + d)
+    }
+}
+
+impl<'a, T: Scalar> Sum<&'a Delta<T>> for Delta<T> {
+    fn sum<I: Iterator<Item = &'a Delta<T>>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl<T: Scalar> From<Direction> for Delta<T> {
+    fn from(d: Direction) -> Self {
+        let (zero, one) = (T::ZERO, T::ONE);
+        match d {
+            Direction::Up => Delta(zero, -one),
+            Direction::Down => Delta(zero, one),
+            Direction::Left => Delta(-one, zero),
+            Direction::Right => Delta(one, zero),
+        }
+    }
+}
+
+impl<T: Scalar> From<Compass> for Delta<T> {
+    fn from(c: Compass) -> Self {
+        let (zero, one) = (T::ZERO, T::ONE);
+        match c {
+            Compass::North => Delta(zero, -one),
+            Compass::NorthEast => Delta(one, -one),
+            Compass::East => Delta(one, zero),
+            Compass::SouthEast => Delta(one, one),
+            Compass::South => Delta(zero, one),
+            Compass::SouthWest => Delta(-one, one),
+            Compass::West => Delta(-one, zero),
+            Compass::NorthWest => Delta(-one, -one),
+        }
+    }
+}
+
+pub struct Line {
+    next: Option<Coord>,
+    end: Coord,
+    dx: isize,
+    dy: isize,
+    step: Delta,
+    err: isize,
+}
+
+impl Iterator for Line {
+    type Item = Coord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        if current == self.end {
+            self.next = None;
+            return Some(current);
+        }
+
+        let mut next = current;
+        let e2 = 2 * self.err;
+        if e2 >= self.dy {
+            self.err += self.dy;
+            next.0 += self.step.0;
+        }
+        if e2 <= self.dx {
+            self.err += self.dx;
+            next.1 += self.step.1;
+        }
+        self.next = Some(next);
+        Some(current)
+    }
+}
+
+// Labels for the components of "<x=1, y=2, z=3>" style text, in order
+const LABELS: [&str; 3] = ["x", "y", "z"];
+
+// Splits "1,2", "(1, 2)" or "<x=1, y=2>" style text into N components.
+// Labels are optional, but must be given for all components or none.
+fn parse_components<T: FromStr, const N: usize>(s: &str) -> Result<[T; N], &'static str> {
+    let s = s.trim();
+    let inner = match s.chars().next() {
+        Some('(') => s.strip_prefix('(').and_then(|s| s.strip_suffix(')')),
+        Some('<') => s.strip_prefix('<').and_then(|s| s.strip_suffix('>')),
+        _ => Some(s),
+    };
+    let inner = inner.ok_or("Unbalanced brackets")?;
+
+    let mut res = [(); N].map(|_| None);
+    let mut parts = inner.split(',');
+    let mut labelled = None;
+    for (i, slot) in res.iter_mut().enumerate() {
+        let part = parts.next().ok_or("Too few components")?;
+        let split = part.split_once('=');
+        if *labelled.get_or_insert(split.is_some()) != split.is_some() {
+            return Err("Mixed labelled and unlabelled components");
+        }
+        let value = match split {
+            None => part,
+            Some((label, value)) => {
+                if LABELS.get(i) != Some(&label.trim()) {
+                    return Err("Invalid component label");
+                }
+                value
+            }
+        };
+        *slot = Some(value.trim().parse().or(Err("Invalid number"))?);
+    }
+    if parts.next().is_some() {
+        return Err("Too many components");
+    }
+    Ok(res.map(Option::unwrap))
+}
+
+fn write_components<T: fmt::Display>(f: &mut fmt::Formatter<'_>, values: &[T]) -> fmt::Result {
+    for (i, v) in values.iter().enumerate() {
+        if i > 0 {
+            write!(f, ",")?;
+        }
+        write!(f, "{}", v)?;
+    }
+    Ok(())
+}
+
+impl<T: Scalar + FromStr> FromStr for Coord<T> {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [x, y] = parse_components(s)?;
+        Ok(Coord(x, y))
+    }
+}
+
+impl<T: Scalar + FromStr> FromStr for Delta<T> {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [x, y] = parse_components(s)?;
+        Ok(Delta(x, y))
+    }
+}
+
+// Written as "x,y", which parses back
+impl<T: fmt::Display> fmt::Display for Coord<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_components(f, &[&self.0, &self.1])
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Delta<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_components(f, &[&self.0, &self.1])
+    }
+}
+
+pub fn manhattan_distance<T: Scalar>(lhs: Coord<T>, rhs: Coord<T>) -> T {
+    (lhs - rhs).manhattan_len()
+}
+
+pub fn chebyshev_distance<T: Scalar>(lhs: Coord<T>, rhs: Coord<T>) -> T {
+    (lhs - rhs).chebyshev_len()
+}
+
+pub fn squared_euclidean_distance<T: Scalar>(lhs: Coord<T>, rhs: Coord<T>) -> T {
+    (lhs - rhs).squared_len()
+}
+
+// Coords are axial (q, r), with the third cube axis implied as -q - r
+pub fn hex_distance<T: Scalar>(lhs: Coord<T>, rhs: Coord<T>) -> T {
+    (lhs - rhs).hex_len()
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct CoordN<const N: usize>(pub [isize; N]);
+
+impl<const N: usize> CoordN<N> {
+    pub fn origin() -> CoordN<N> {
+        CoordN([0; N])
+    }
+
+    pub fn manhattan_distance(self, other: CoordN<N>) -> isize {
+        (self - other).manhattan_len()
+    }
+
+    pub fn chebyshev_distance(self, other: CoordN<N>) -> isize {
+        (self - other).chebyshev_len()
+    }
+
+    pub fn squared_euclidean_distance(self, other: CoordN<N>) -> isize {
+        (self - other).squared_len()
+    }
+
+    // Every coordinate within Chebyshev distance `radius`, except `self`
+    pub fn moore(self, radius: usize) -> impl Iterator<Item = CoordN<N>> {
+        DeltaN::moore(radius).map(move |d| self + d)
+    }
+
+    // Every coordinate within Manhattan distance `radius`, except `self`
+    pub fn von_neumann(self, radius: usize) -> impl Iterator<Item = CoordN<N>> {
+        DeltaN::von_neumann(radius).map(move |d| self + d)
+    }
+
+    pub fn component_min(self, other: CoordN<N>) -> CoordN<N> {
+        CoordN(zip_with(self.0, other.0, isize::min))
+    }
+
+    pub fn component_max(self, other: CoordN<N>) -> CoordN<N> {
+        CoordN(zip_with(self.0, other.0, isize::max))
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct DeltaN<const N: usize>(pub [isize; N]);
+
+impl<const N: usize> DeltaN<N> {
+    pub fn zero() -> DeltaN<N> {
+        DeltaN([0; N])
+    }
+
+    pub fn manhattan_len(self) -> isize {
+        self.0.iter().map(|a| a.abs()).sum()
+    }
+
+    pub fn chebyshev_len(self) -> isize {
+        self.0.iter().map(|a| a.abs()).max().unwrap_or(0)
+    }
+
+    pub fn squared_len(self) -> isize {
+        self.0.iter().map(|a| a * a).sum()
+    }
+
+    // All non-zero deltas with every component in -radius..=radius
+    pub fn moore(radius: usize) -> impl Iterator<Item = DeltaN<N>> {
+        let r = radius as isize;
+        BoxN::new(CoordN([-r; N]), CoordN([r; N]))
+            .coords()
+            .map(|c| c - CoordN::origin())
+            .filter(|d| *d != DeltaN::zero())
+    }
+
+    pub fn von_neumann(radius: usize) -> impl Iterator<Item = DeltaN<N>> {
+        Self::moore(radius).filter(move |d| d.manhattan_len() <= radius as isize)
+    }
+
+    pub fn component_min(self, other: DeltaN<N>) -> DeltaN<N> {
+        DeltaN(zip_with(self.0, other.0, isize::min))
+    }
+
+    pub fn component_max(self, other: DeltaN<N>) -> DeltaN<N> {
+        DeltaN(zip_with(self.0, other.0, isize::max))
+    }
+}
+
+fn zip_with<const N: usize>(
+    lhs: [isize; N],
+    rhs: [isize; N],
+    f: impl Fn(isize, isize) -> isize,
+) -> [isize; N] {
+    std::array::from_fn(|i| f(lhs[i], rhs[i]))
+}
+
+impl<const N: usize> Add<DeltaN<N>> for CoordN<N> {
+    type Output = CoordN<N>;
+
+    fn add(self, rhs: DeltaN<N>) -> Self::Output {
+        CoordN(zip_with(self.0, rhs.0, |a, b| a + b))
+    }
+}
+
+impl<const N: usize> AddAssign<DeltaN<N>> for CoordN<N> {
+    fn add_assign(&mut self, rhs: DeltaN<N>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: usize> Sub<DeltaN<N>> for CoordN<N> {
+    type Output = CoordN<N>;
+
+    fn sub(self, rhs: DeltaN<N>) -> Self::Output {
+        CoordN(zip_with(self.0, rhs.0, |a, b| a - b))
+    }
+}
+
+impl<const N: usize> SubAssign<DeltaN<N>> for CoordN<N> {
+    fn sub_assign(&mut self, rhs: DeltaN<N>) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const N: usize> Sub<CoordN<N>> for CoordN<N> {
+    type Output = DeltaN<N>;
+
+    fn sub(self, rhs: CoordN<N>) -> Self::Output {
+        DeltaN(zip_with(self.0, rhs.0, |a, b| a - b))
+    }
+}
+
+impl<const N: usize> Add<DeltaN<N>> for DeltaN<N> {
+    type Output = DeltaN<N>;
+
+    fn add(self, rhs: DeltaN<N>) -> Self::Output {
+        DeltaN(zip_with(self.0, rhs.0, |a, b| a + b))
+    }
+}
+
+impl<const N: usize> AddAssign<DeltaN<N>> for DeltaN<N> {
+    fn add_assign(&mut self, rhs: DeltaN<N>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: usize> Sub<DeltaN<N>> for DeltaN<N> {
+    type Output = DeltaN<N>;
+
+    fn sub(self, rhs: DeltaN<N>) -> Self::Output {
+        DeltaN(zip_with(self.0, rhs.0, |a, b| a - b))
+    }
+}
+
+impl<const N: usize> SubAssign<DeltaN<N>> for DeltaN<N> {
+    fn sub_assign(&mut self, rhs: DeltaN<N>) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const N: usize> Neg for DeltaN<N> {
+    type Output = DeltaN<N>;
+
+    fn neg(self) -> Self::Output {
+        DeltaN(self.0.map(|a| -a))
+    }
+}
+
+impl<const N: usize> Mul<DeltaN<N>> for isize {
+    type Output = DeltaN<N>;
+
+    fn mul(self, rhs: DeltaN<N>) -> Self::Output {
+        DeltaN(rhs.0.map(|a| self * a))
+    }
+}
+
+impl<const N: usize> Div<isize> for DeltaN<N> {
+    type Output = DeltaN<N>;
+
+    fn div(self, rhs: isize) -> Self::Output {
+        DeltaN(self.0.map(|a| a / rhs))
+    }
+}
+
+impl<const N: usize> Sum for DeltaN<N> {
+    fn sum<I: Iterator<Item = DeltaN<N>>>(iter: I) -> Self {
+        iter.fold(DeltaN::zero(), |acc, d| acc + d)
+    }
+}
+
+impl<'a, const N: usize> Sum<&'a DeltaN<N>> for DeltaN<N> {
+    fn sum<I: Iterator<Item = &'a DeltaN<N>>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl<const N: usize> FromStr for CoordN<N> {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(CoordN(parse_components(s)?))
+    }
+}
+
+impl<const N: usize> FromStr for DeltaN<N> {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(DeltaN(parse_components(s)?))
+    }
+}
+
+impl<const N: usize> fmt::Display for CoordN<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_components(f, &self.0)
+    }
+}
+
+impl<const N: usize> fmt::Display for DeltaN<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_components(f, &self.0)
+    }
+}
+
+// Embeds 2D points into the first two axes, with zeros elsewhere. Converting
+// to fewer than two dimensions fails to compile.
+impl<const N: usize> From<Coord> for CoordN<N> {
+    fn from(c: Coord) -> Self {
+        const { assert!(N >= 2) };
+        let mut res = [0; N];
+        res[0] = c.0;
+        res[1] = c.1;
+        CoordN(res)
+    }
+}
+
+impl<const N: usize> From<Delta> for DeltaN<N> {
+    fn from(d: Delta) -> Self {
+        const { assert!(N >= 2) };
+        let mut res = [0; N];
+        res[0] = d.0;
+        res[1] = d.1;
+        DeltaN(res)
+    }
+}
+
+impl From<CoordN<2>> for Coord {
+    fn from(c: CoordN<2>) -> Self {
+        Coord(c.0[0], c.0[1])
+    }
+}
+
+impl From<DeltaN<2>> for Delta {
+    fn from(d: DeltaN<2>) -> Self {
+        Delta(d.0[0], d.0[1])
+    }
+}
+
+// Axis-aligned bounding box, inclusive of both corners, so it always holds
+// at least one coordinate
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Rect {
+    pub min: Coord,
+    pub max: Coord,
+}
+
+impl Rect {
+    pub fn new(a: Coord, b: Coord) -> Rect {
+        Rect {
+            min: a.component_min(b),
+            max: a.component_max(b),
+        }
+    }
+
+    pub fn from_points<I: IntoIterator<Item = Coord>>(points: I) -> Option<Rect> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Rect::new(first, first), Rect::extended_to))
+    }
+
+    pub fn extended_to(self, c: Coord) -> Rect {
+        Rect {
+            min: self.min.component_min(c),
+            max: self.max.component_max(c),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.0 - self.min.0 + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.1 - self.min.1 + 1) as usize
+    }
+
+    pub fn area(&self) -> usize {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, c: Coord) -> bool {
+        (self.min.0..=self.max.0).contains(&c.0) && (self.min.1..=self.max.1).contains(&c.1)
+    }
+
+    // The smallest rect containing both
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect {
+            min: self.min.component_min(other.min),
+            max: self.max.component_max(other.max),
+        }
+    }
+
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let min = self.min.component_max(other.min);
+        let max = self.max.component_min(other.max);
+        if min.0 > max.0 || min.1 > max.1 {
+            return None;
+        }
+        Some(Rect { min, max })
+    }
+
+    pub fn expanded(&self, margin: usize) -> Rect {
+        let m = margin as isize;
+        Rect {
+            min: self.min - Delta(m, m),
+            max: self.max + Delta(m, m),
+        }
+    }
+
+    // Top-left, top-right, bottom-left and bottom-right
+    pub fn corners(&self) -> [Coord; 4] {
+        [
+            self.min,
+            Coord(self.max.0, self.min.1),
+            Coord(self.min.0, self.max.1),
+            self.max,
+        ]
+    }
+
+    // Row by row, left to right
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let Rect { min, max } = *self;
+        (min.1..=max.1).flat_map(move |y| (min.0..=max.0).map(move |x| Coord(x, y)))
+    }
+}
+
+// N-dimensional version of `Rect`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct BoxN<const N: usize> {
+    pub min: CoordN<N>,
+    pub max: CoordN<N>,
+}
+
+impl<const N: usize> BoxN<N> {
+    pub fn new(a: CoordN<N>, b: CoordN<N>) -> BoxN<N> {
+        BoxN {
+            min: a.component_min(b),
+            max: a.component_max(b),
+        }
+    }
+
+    pub fn from_points<I: IntoIterator<Item = CoordN<N>>>(points: I) -> Option<BoxN<N>> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(BoxN::new(first, first), BoxN::extended_to))
+    }
+
+    pub fn extended_to(self, c: CoordN<N>) -> BoxN<N> {
+        BoxN {
+            min: self.min.component_min(c),
+            max: self.max.component_max(c),
+        }
+    }
+
+    pub fn len(&self, axis: usize) -> usize {
+        (self.max.0[axis] - self.min.0[axis] + 1) as usize
+    }
+
+    pub fn volume(&self) -> usize {
+        (0..N).map(|axis| self.len(axis)).product()
+    }
+
+    pub fn contains(&self, c: CoordN<N>) -> bool {
+        (0..N).all(|i| (self.min.0[i]..=self.max.0[i]).contains(&c.0[i]))
+    }
+
+    pub fn union(&self, other: &BoxN<N>) -> BoxN<N> {
+        BoxN {
+            min: self.min.component_min(other.min),
+            max: self.max.component_max(other.max),
+        }
+    }
+
+    pub fn intersection(&self, other: &BoxN<N>) -> Option<BoxN<N>> {
+        let min = self.min.component_max(other.min);
+        let max = self.max.component_min(other.max);
+        if (0..N).any(|i| min.0[i] > max.0[i]) {
+            return None;
+        }
+        Some(BoxN { min, max })
+    }
+
+    pub fn expanded(&self, margin: usize) -> BoxN<N> {
+        let m = DeltaN([margin as isize; N]);
+        BoxN {
+            min: self.min - m,
+            max: self.max + m,
+        }
+    }
+
+    // Row-major order: the first axis varies fastest, the last slowest
+    pub fn coords(&self) -> impl Iterator<Item = CoordN<N>> {
+        BoxCoords {
+            bounds: *self,
+            next: Some(self.min),
+        }
+    }
+}
+
+// Counts through a box like an odometer
+struct BoxCoords<const N: usize> {
+    bounds: BoxN<N>,
+    next: Option<CoordN<N>>,
+}
+
+impl<const N: usize> Iterator for BoxCoords<N> {
+    type Item = CoordN<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        let mut digits = current.0;
+        self.next = None;
+        for (i, digit) in digits.iter_mut().enumerate() {
+            if *digit != self.bounds.max.0[i] {
+                *digit += 1;
+                self.next = Some(CoordN(digits));
+                break;
+            }
+            *digit = self.bounds.min.0[i];
+        }
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn coord_arithmetic() {
+        let a = Coord(3, -4);
+        let b = Coord(-1, 2);
+        let d = a - b;
+        assert_eq!(d, Delta(4, -6));
+        assert_eq!(b + d, a);
+        assert_eq!(a - d, b);
+
+        let mut c = a;
+        c -= d;
+        assert_eq!(c, b);
+        c += d;
+        assert_eq!(c, a);
+    }
+
+    #[test]
+    fn delta_arithmetic() {
+        let d = Delta(6, -9);
+        assert_eq!(-d, Delta(-6, 9));
+        assert_eq!(d - d, Delta(0, 0));
+        assert_eq!(d + -d, Delta(0, 0));
+        assert_eq!(d / 3, Delta(2, -3));
+        assert_eq!(Delta(7, -7) / 2, Delta(3, -3));
+        assert_eq!((2 * d) / 2, d);
+
+        let mut e = d;
+        e -= Delta(1, 1);
+        assert_eq!(e, Delta(5, -10));
+    }
+
+    #[test]
+    fn delta_sum() {
+        let deltas = [Delta(1, 0), Delta(0, 1), Delta(-3, 2)];
+        assert_eq!(deltas.iter().sum::<Delta>(), Delta(-2, 3));
+        assert_eq!(deltas.iter().copied().sum::<Delta>(), Delta(-2, 3));
+        assert_eq!(std::iter::empty::<Delta>().sum::<Delta>(), Delta(0, 0));
+        assert_eq!(
+            [DeltaN([1, 2, 3]), DeltaN([1, 1, 1])]
+                .iter()
+                .sum::<DeltaN<3>>(),
+            DeltaN([2, 3, 4])
+        );
+    }
+
+    #[test]
+    fn component_min_max() {
+        let a = Coord(1, 5);
+        let b = Coord(3, -2);
+        assert_eq!(a.component_min(b), Coord(1, -2));
+        assert_eq!(a.component_max(b), Coord(3, 5));
+        assert_eq!(Delta(1, 5).component_min(Delta(3, -2)), Delta(1, -2));
+        assert_eq!(Delta(1, 5).component_max(Delta(3, -2)), Delta(3, 5));
+        assert_eq!(
+            CoordN([1, 5, 0]).component_min(CoordN([3, -2, 0])),
+            CoordN([1, -2, 0])
+        );
+    }
+
+    #[test]
+    fn distances() {
+        let a = Coord(1, 2);
+        let b = Coord(4, -2);
+        assert_eq!(manhattan_distance(a, b), 7);
+        assert_eq!(chebyshev_distance(a, b), 4);
+        assert_eq!(squared_euclidean_distance(a, b), 25);
+        assert_eq!(manhattan_distance(a, a), 0);
+
+        assert_eq!(Delta(-3, 4).manhattan_len(), 7);
+        assert_eq!(Delta(-3, 4).chebyshev_len(), 4);
+        assert_eq!(Delta(-3, 4).squared_len(), 25);
+    }
+
+    #[test]
+    fn hex_distances() {
+        // The six axial neighbours are all one step away
+        for d in &[(1, 0), (1, -1), (0, -1), (-1, 0), (0, 1), (-1, 1)] {
+            assert_eq!(Delta(d.0, d.1).hex_len(), 1);
+        }
+        assert_eq!(Delta(1, 1).hex_len(), 2);
+        assert_eq!(Delta(2, -1).hex_len(), 2);
+        assert_eq!(hex_distance(Coord(3, -3), Coord(-3, 3)), 6);
+        assert_eq!(hex_distance(Coord(3, 3), Coord(0, 0)), 6);
+    }
+
+    #[test]
+    fn distances_n() {
+        let a = CoordN([1, 2, 3, 4]);
+        let b = CoordN([0, 4, 3, -1]);
+        assert_eq!(a.manhattan_distance(b), 8);
+        assert_eq!(a.chebyshev_distance(b), 5);
+        assert_eq!(a.squared_euclidean_distance(b), 30);
+        assert_eq!(DeltaN::<0>::zero().chebyshev_len(), 0);
+    }
+
+    #[test]
+    fn neighbourhoods_2d() {
+        let c = Coord(5, -3);
+        let vn: HashSet<Coord> = c.von_neumann().collect();
+        assert_eq!(vn.len(), 4);
+        assert!(vn.iter().all(|&n| manhattan_distance(c, n) == 1));
+
+        let moore: HashSet<Coord> = c.moore().collect();
+        assert_eq!(moore.len(), 8);
+        assert!(moore.iter().all(|&n| chebyshev_distance(c, n) == 1));
+        assert!(moore.is_superset(&vn));
+    }
+
+    #[test]
+    fn neighbourhoods_n() {
+        let c = CoordN([1, 2, 3]);
+        let moore: HashSet<_> = c.moore(1).collect();
+        assert_eq!(moore.len(), 26);
+        assert!(!moore.contains(&c));
+        assert_eq!(c.moore(2).count(), 124);
+        assert!(c.moore(2).all(|n| c.chebyshev_distance(n) <= 2));
+
+        assert_eq!(c.von_neumann(1).count(), 6);
+        assert_eq!(c.von_neumann(2).count(), 24);
+        assert!(c.von_neumann(2).all(|n| c.manhattan_distance(n) <= 2));
+
+        assert_eq!(DeltaN::<4>::moore(1).count(), 80);
+        assert_eq!(DeltaN::<2>::moore(0).count(), 0);
+        let moore_2d: HashSet<Delta> = DeltaN::<2>::moore(1).map(Delta::from).collect();
+        assert_eq!(moore_2d, Delta::MOORE.iter().copied().collect());
+    }
+
+    #[test]
+    fn compass_turns() {
+        assert_eq!(Compass::North.turn_45(1), Compass::NorthEast);
+        assert_eq!(Compass::North.turn_45(-1), Compass::NorthWest);
+        assert_eq!(Compass::West.turn_45(3), Compass::NorthEast);
+        assert_eq!(Compass::SouthEast.turn(Turn::Right), Compass::SouthWest);
+        assert_eq!(Compass::SouthEast.turn(Turn::Back), Compass::NorthWest);
+        for &c in &Compass::ALL {
+            assert_eq!(c.turn_45(8), c);
+            assert_eq!(<Delta>::from(c.turn(Turn::Back)), -<Delta>::from(c));
+            assert_eq!(<Delta>::from(c).chebyshev_len(), 1);
+        }
+    }
+
+    #[test]
+    fn compass_direction_conversion() {
+        for &d in &[
+            Direction::Up,
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+        ] {
+            let c = Compass::from(d);
+            assert_eq!(<Delta>::from(c), <Delta>::from(d));
+            assert_eq!(Direction::try_from(c), Ok(d));
+            assert_eq!(Compass::from(d.turn(Turn::Right)), c.turn(Turn::Right));
+        }
+        assert!(Direction::try_from(Compass::SouthWest).is_err());
+    }
+
+    #[test]
+    fn compass_parsing() {
+        for s in &["N", "n", "U", "^"] {
+            assert_eq!(s.parse(), Ok(Compass::North));
+        }
+        assert_eq!(Compass::try_from('v'), Ok(Compass::South));
+        assert_eq!(Compass::try_from('<'), Ok(Compass::West));
+        assert_eq!(Compass::try_from('R'), Ok(Compass::East));
+        assert_eq!("NE".parse(), Ok(Compass::NorthEast));
+        assert_eq!("sw".parse(), Ok(Compass::SouthWest));
+        assert!("EN".parse::<Compass>().is_err());
+        assert_eq!("nE".parse(), Ok(Compass::NorthEast));
+        for s in &["UR", "DL", "Ur", "nR", "U>"] {
+            assert_eq!(s.parse::<Compass>(), Err("Invalid diagonal direction"));
+        }
+        assert!("^>".parse::<Compass>().is_err());
+        assert!("NEE".parse::<Compass>().is_err());
+        assert!("".parse::<Compass>().is_err());
+        assert!(Compass::try_from('x').is_err());
+    }
+
+    #[test]
+    fn rays() {
+        let ray: Vec<_> = Coord(1, 1).ray(Delta(3, -1)).take(3).collect();
+        assert_eq!(ray, [Coord(4, 0), Coord(7, -1), Coord(10, -2)]);
+        assert!(Coord(0, 0)
+            .ray(Delta(0, 0))
+            .take(5)
+            .all(|c| c == Coord(0, 0)));
+
+        assert_eq!(Delta(4, -6).reduced(), Delta(2, -3));
+        assert_eq!(Delta(0, -5).reduced(), Delta(0, -1));
+        assert_eq!(Delta(0, 0).reduced(), Delta(0, 0));
+        assert_eq!(Delta(-7, 3).reduced(), Delta(-7, 3));
+    }
+
+    #[test]
+    fn lines() {
+        let line: Vec<_> = Coord(0, 0).line_to(Coord(6, 2)).collect();
+        let expected = [(0, 0), (1, 0), (2, 1), (3, 1), (4, 1), (5, 2), (6, 2)];
+        assert_eq!(line, expected.map(|(x, y)| Coord(x, y)));
+        assert_eq!(
+            Coord(2, 2).line_to(Coord(2, 2)).collect::<Vec<_>>(),
+            [Coord(2, 2)]
+        );
+
+        let ends = [
+            Coord(0, 0),
+            Coord(5, -3),
+            Coord(-2, 7),
+            Coord(-6, -6),
+            Coord(3, 0),
+        ];
+        for &a in &ends {
+            for &b in &ends {
+                let line: Vec<_> = a.line_to(b).collect();
+                assert_eq!(line.first(), Some(&a));
+                assert_eq!(line.last(), Some(&b));
+                assert_eq!(line.len() as isize, chebyshev_distance(a, b) + 1);
+                assert!(line.windows(2).all(|w| chebyshev_distance(w[0], w[1]) == 1));
+            }
+        }
+    }
+
+    #[test]
+    fn other_scalars() {
+        let big: Coord<i64> = Coord(5_000_000_000, -3);
+        let d: Delta<i64> = Compass::SouthEast.into();
+        assert_eq!(big + 2 * d, Coord(5_000_000_002, -1));
+        assert_eq!(manhattan_distance(big, Coord::origin()), 5_000_000_003);
+        assert_eq!(Delta(3i128, -4).chebyshev_len(), 4);
+        assert_eq!(Delta(6i16, 4).reduced(), Delta(3, 2));
+
+        assert_eq!(Coord(7i64, -2).try_cast::<i8>(), Some(Coord(7i8, -2)));
+        assert_eq!(big.try_cast::<i32>(), None);
+        assert_eq!(Delta(300i32, 0).try_cast::<i8>(), None);
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let c = Coord(i8::MAX - 1, 0);
+        assert_eq!(c.checked_add(Delta(1, 1)), Some(Coord(i8::MAX, 1)));
+        assert_eq!(c.checked_add(Delta(2, 0)), None);
+        assert_eq!(Coord(0i8, i8::MIN).checked_sub(Delta(0, 1)), None);
+        assert_eq!(c.overflowing_add(Delta(2, 0)), (Coord(i8::MIN, 0), true));
+        assert_eq!(
+            c.overflowing_sub(Delta(1, 0)),
+            (Coord(i8::MAX - 2, 0), false)
+        );
+
+        assert_eq!(Coord(-100i8, 0).checked_delta_to(Coord(100, 0)), None);
+        assert_eq!(
+            Coord(-10i8, 0).checked_delta_to(Coord(10, 5)),
+            Some(Delta(20, 5))
+        );
+
+        let d = Delta(100i8, -100);
+        assert_eq!(d.checked_mul(2), None);
+        assert_eq!(d.checked_mul(-1), Some(Delta(-100, 100)));
+        assert_eq!(d.checked_add(d), None);
+        assert_eq!(d.checked_sub(Delta(0, 28)), Some(Delta(100, -128)));
+        assert_eq!(Delta(i8::MIN, 0).checked_neg(), None);
+        assert_eq!(d.overflowing_mul(2), (Delta(-56, 56), true));
+        assert_eq!(d.overflowing_add(Delta(1, 1)), (Delta(101, -99), false));
+        assert!(Delta(0i8, i8::MIN).overflowing_sub(Delta(0, 1)).1);
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!("3,-4".parse(), Ok(Coord(3, -4)));
+        assert_eq!("(3, -4)".parse(), Ok(Coord(3, -4)));
+        assert_eq!(" <x=3, y=-4> ".parse(), Ok(Delta(3, -4)));
+        assert_eq!("<x=-1, y=0, z=2>".parse(), Ok(CoordN([-1, 0, 2])));
+        assert_eq!("1,2,3,4".parse(), Ok(DeltaN([1, 2, 3, 4])));
+        assert_eq!("9000000000,1".parse(), Ok(Coord(9_000_000_000i64, 1)));
+
+        assert!("1,2,3".parse::<Coord>().is_err());
+        assert!("1".parse::<Coord>().is_err());
+        assert!("(1,2".parse::<Coord>().is_err());
+        assert!("<1,2)".parse::<Coord>().is_err());
+        assert!("1,,2".parse::<CoordN<3>>().is_err());
+        assert!("x=1,2=2".parse::<Coord>().is_err());
+        assert_eq!(
+            "<y=1, x=2>".parse::<Coord>(),
+            Err("Invalid component label")
+        );
+        assert_eq!(
+            "<q=1, x=2>".parse::<Coord>(),
+            Err("Invalid component label")
+        );
+        assert_eq!(
+            "<x=1, 2>".parse::<Coord>(),
+            Err("Mixed labelled and unlabelled components")
+        );
+        assert_eq!(
+            "1, y=2".parse::<Coord>(),
+            Err("Mixed labelled and unlabelled components")
+        );
+        assert_eq!(
+            "<x=1, y=2, z=3, w=4>".parse::<CoordN<4>>(),
+            Err("Invalid component label")
+        );
+        assert!("300,0".parse::<Coord<i8>>().is_err());
+        assert!("".parse::<Coord>().is_err());
+    }
+
+    #[test]
+    fn fuzz_parse_coords() {
+        let samples = ["1,2,3", "(1, -2, 3)", "<x=1, y=2, z=-3>"];
+        crate::fuzz::check_parser(&samples, |s| s.parse::<CoordN<3>>());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Coord(3, -4).to_string(), "3,-4");
+        assert_eq!(Delta(0i8, 1).to_string(), "0,1");
+        assert_eq!(CoordN([1, -2, 3]).to_string(), "1,-2,3");
+        assert_eq!(DeltaN::<0>::zero().to_string(), "");
+
+        let c = CoordN([7, 8, -9, 10]);
+        assert_eq!(c.to_string().parse(), Ok(c));
+    }
+
+    #[test]
+    fn rect() {
+        let r = Rect::from_points(vec![Coord(3, 1), Coord(-1, 4), Coord(0, 0)]).unwrap();
+        assert_eq!(r, Rect::new(Coord(3, 4), Coord(-1, 0)));
+        assert_eq!((r.width(), r.height(), r.area()), (5, 5, 25));
+        assert!(r.contains(Coord(-1, 0)) && r.contains(Coord(3, 4)));
+        assert!(!r.contains(Coord(4, 0)) && !r.contains(Coord(0, -1)));
+        assert_eq!(Rect::from_points(vec![]), None);
+
+        let coords: Vec<_> = Rect::new(Coord(0, 0), Coord(1, 1)).coords().collect();
+        assert_eq!(coords, [Coord(0, 0), Coord(1, 0), Coord(0, 1), Coord(1, 1)]);
+        assert_eq!(r.coords().count(), r.area());
+        assert!(r.coords().all(|c| r.contains(c)));
+        assert_eq!(r.corners()[1], Coord(3, 0));
+
+        let other = Rect::new(Coord(2, 3), Coord(10, 10));
+        assert_eq!(r.union(&other), Rect::new(Coord(-1, 0), Coord(10, 10)));
+        assert_eq!(
+            r.intersection(&other),
+            Some(Rect::new(Coord(2, 3), Coord(3, 4)))
+        );
+        assert_eq!(r.intersection(&Rect::new(Coord(4, 0), Coord(5, 5))), None);
+        assert_eq!(r.expanded(2), Rect::new(Coord(-3, -2), Coord(5, 6)));
+    }
+
+    #[test]
+    fn box_n() {
+        let b = BoxN::from_points(vec![CoordN([0, 0, 0]), CoordN([2, -1, 3])]).unwrap();
+        assert_eq!(b.min, CoordN([0, -1, 0]));
+        assert_eq!((b.len(0), b.len(1), b.len(2)), (3, 2, 4));
+        assert_eq!(b.volume(), 24);
+        assert_eq!(b.coords().count(), 24);
+        assert!(b.coords().all(|c| b.contains(c)));
+        assert!(!b.contains(CoordN([0, 1, 0])));
+
+        let coords: Vec<_> = BoxN::new(CoordN([0, 0]), CoordN([1, 1])).coords().collect();
+        assert_eq!(
+            coords,
+            [
+                CoordN([0, 0]),
+                CoordN([1, 0]),
+                CoordN([0, 1]),
+                CoordN([1, 1])
+            ]
+        );
+
+        let other = BoxN::new(CoordN([1, 0, 3]), CoordN([5, 5, 5]));
+        assert_eq!(
+            b.intersection(&other),
+            Some(BoxN::new(CoordN([1, 0, 3]), CoordN([2, 0, 3])))
+        );
+        assert_eq!(b.union(&other).volume(), 6 * 7 * 6);
+        assert_eq!(b.expanded(1).volume(), 5 * 4 * 6);
+        assert_eq!(b.intersection(&b.expanded(1)), Some(b));
+    }
+
+    #[test]
+    fn delta_ord_hash() {
+        let mut deltas = vec![Delta(1, 0), Delta(0, 1), Delta(0, -1), Delta(1, 0)];
+        deltas.sort();
+        assert_eq!(
+            deltas,
+            [Delta(0, -1), Delta(0, 1), Delta(1, 0), Delta(1, 0)]
+        );
+        let unique: HashSet<Delta> = deltas.into_iter().collect();
+        assert_eq!(unique.len(), 3);
+    }
+
+    #[test]
+    fn coord_n_arithmetic() {
+        let c = CoordN([1, 2, 3]);
+        let d = DeltaN([-1, 0, 4]);
+        assert_eq!(c + d, CoordN([0, 2, 7]));
+        assert_eq!(c - d, CoordN([2, 2, -1]));
+        assert_eq!((c + d) - c, d);
+        assert_eq!(-d, DeltaN([1, 0, -4]));
+        assert_eq!(3 * d, d + d + d);
+
+        let mut m = c;
+        m += d;
+        m -= d;
+        assert_eq!(m, c);
+    }
+
+    #[test]
+    fn coord_n_from_2d() {
+        let c: CoordN<4> = Coord(5, -2).into();
+        assert_eq!(c, CoordN([5, -2, 0, 0]));
+        assert_eq!(Coord::from(CoordN([7, 8])), Coord(7, 8));
+        assert_eq!(DeltaN::<3>::from(Delta(1, 1)), DeltaN([1, 1, 0]));
+    }
+}
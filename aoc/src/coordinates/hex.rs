@@ -0,0 +1,434 @@
+// Hexagonal grids in axial coordinates (q, r), with the third cube axis
+// implied as s = -q - r. Both layouts share the same axes: q grows to the
+// east and r grows to the south, matching the screen-style `Coord`.
+use super::{Coord, Delta};
+use std::convert::TryFrom;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Hex(pub isize, pub isize);
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct HexDelta(pub isize, pub isize);
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Cube(pub isize, pub isize, pub isize);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HexLayout {
+    FlatTop,
+    PointyTop,
+}
+
+// Neighbours of a pointy-top hex, which has flat east and west sides
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PointyDir {
+    East,
+    NorthEast,
+    NorthWest,
+    West,
+    SouthWest,
+    SouthEast,
+}
+
+// Neighbours of a flat-top hex, which has flat north and south sides
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FlatDir {
+    North,
+    NorthEast,
+    SouthEast,
+    South,
+    SouthWest,
+    NorthWest,
+}
+
+// All six unit steps in counter-clockwise order, starting east
+const UNIT_DELTAS: [HexDelta; 6] = [
+    HexDelta(1, 0),
+    HexDelta(1, -1),
+    HexDelta(0, -1),
+    HexDelta(-1, 0),
+    HexDelta(-1, 1),
+    HexDelta(0, 1),
+];
+
+impl Hex {
+    pub fn origin() -> Hex {
+        Hex(0, 0)
+    }
+
+    pub fn neighbours(self) -> impl Iterator<Item = Hex> {
+        UNIT_DELTAS.iter().map(move |&d| self + d)
+    }
+
+    pub fn distance(self, other: Hex) -> isize {
+        (self - other).hex_len()
+    }
+
+    // Rotates around `center` by `steps` sixths of a turn clockwise
+    pub fn rotate_around(self, center: Hex, steps: isize) -> Hex {
+        center + (self - center).rotate(steps)
+    }
+
+    // The 6 * radius hexes exactly `radius` steps away, or just `self` for
+    // radius 0
+    pub fn ring(self, radius: usize) -> impl Iterator<Item = Hex> {
+        let count = (6 * radius).max(1);
+        (0..count).map(move |k| {
+            if radius == 0 {
+                return self;
+            }
+            let (side, step) = (k / radius, k % radius);
+            let corner = self + radius as isize * UNIT_DELTAS[(side + 4) % 6];
+            corner + step as isize * UNIT_DELTAS[side]
+        })
+    }
+
+    // All hexes within `radius` steps, ordered by distance from `self`
+    pub fn spiral(self, radius: usize) -> impl Iterator<Item = Hex> {
+        (0..=radius).flat_map(move |r| self.ring(r))
+    }
+
+    // Centre of the hex in pixels, for hexes of the given outer radius
+    pub fn to_pixel(self, layout: HexLayout, size: f64) -> (f64, f64) {
+        let sqrt3 = 3f64.sqrt();
+        let (q, r) = (self.0 as f64, self.1 as f64);
+        match layout {
+            HexLayout::PointyTop => (size * sqrt3 * (q + r / 2.0), size * 1.5 * r),
+            HexLayout::FlatTop => (size * 1.5 * q, size * sqrt3 * (r + q / 2.0)),
+        }
+    }
+}
+
+impl HexDelta {
+    pub fn hex_len(self) -> isize {
+        Delta(self.0, self.1).hex_len()
+    }
+
+    pub fn rotate(self, steps: isize) -> HexDelta {
+        let mut c = Cube::from(Hex(self.0, self.1));
+        for _ in 0..steps.rem_euclid(6) {
+            c = Cube(-c.1, -c.2, -c.0);
+        }
+        HexDelta(c.0, c.1)
+    }
+}
+
+impl Cube {
+    pub fn distance(self, other: Cube) -> isize {
+        let d = (self.0 - other.0, self.1 - other.1, self.2 - other.2);
+        d.0.abs().max(d.1.abs()).max(d.2.abs())
+    }
+}
+
+impl From<Hex> for Cube {
+    fn from(h: Hex) -> Self {
+        Cube(h.0, h.1, -h.0 - h.1)
+    }
+}
+
+impl TryFrom<Cube> for Hex {
+    type Error = &'static str;
+
+    fn try_from(c: Cube) -> Result<Self, Self::Error> {
+        if c.0 + c.1 + c.2 != 0 {
+            return Err("Cube coordinates don't sum to zero");
+        }
+        Ok(Hex(c.0, c.1))
+    }
+}
+
+impl From<Hex> for Coord {
+    fn from(h: Hex) -> Self {
+        Coord(h.0, h.1)
+    }
+}
+
+impl From<Coord> for Hex {
+    fn from(c: Coord) -> Self {
+        Hex(c.0, c.1)
+    }
+}
+
+impl PointyDir {
+    pub const ALL: [PointyDir; 6] = [
+        PointyDir::East,
+        PointyDir::NorthEast,
+        PointyDir::NorthWest,
+        PointyDir::West,
+        PointyDir::SouthWest,
+        PointyDir::SouthEast,
+    ];
+}
+
+impl FlatDir {
+    pub const ALL: [FlatDir; 6] = [
+        FlatDir::North,
+        FlatDir::NorthEast,
+        FlatDir::SouthEast,
+        FlatDir::South,
+        FlatDir::SouthWest,
+        FlatDir::NorthWest,
+    ];
+}
+
+impl From<PointyDir> for HexDelta {
+    fn from(d: PointyDir) -> Self {
+        match d {
+            PointyDir::East => HexDelta(1, 0),
+            PointyDir::NorthEast => HexDelta(1, -1),
+            PointyDir::NorthWest => HexDelta(0, -1),
+            PointyDir::West => HexDelta(-1, 0),
+            PointyDir::SouthWest => HexDelta(-1, 1),
+            PointyDir::SouthEast => HexDelta(0, 1),
+        }
+    }
+}
+
+impl From<FlatDir> for HexDelta {
+    fn from(d: FlatDir) -> Self {
+        match d {
+            FlatDir::North => HexDelta(0, -1),
+            FlatDir::NorthEast => HexDelta(1, -1),
+            FlatDir::SouthEast => HexDelta(1, 0),
+            FlatDir::South => HexDelta(0, 1),
+            FlatDir::SouthWest => HexDelta(-1, 1),
+            FlatDir::NorthWest => HexDelta(-1, 0),
+        }
+    }
+}
+
+impl FromStr for PointyDir {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "e" => Ok(PointyDir::East),
+            "ne" => Ok(PointyDir::NorthEast),
+            "nw" => Ok(PointyDir::NorthWest),
+            "w" => Ok(PointyDir::West),
+            "sw" => Ok(PointyDir::SouthWest),
+            "se" => Ok(PointyDir::SouthEast),
+            _ => Err("Invalid hex direction"),
+        }
+    }
+}
+
+impl FromStr for FlatDir {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "n" => Ok(FlatDir::North),
+            "ne" => Ok(FlatDir::NorthEast),
+            "se" => Ok(FlatDir::SouthEast),
+            "s" => Ok(FlatDir::South),
+            "sw" => Ok(FlatDir::SouthWest),
+            "nw" => Ok(FlatDir::NorthWest),
+            _ => Err("Invalid hex direction"),
+        }
+    }
+}
+
+// Parses an unseparated path such as "esenee" into pointy-top directions
+pub fn parse_path(s: &str) -> Result<Vec<PointyDir>, &'static str> {
+    let mut res = Vec::new();
+    let mut rest = s;
+    while !rest.is_empty() {
+        let len = match rest.as_bytes()[0] {
+            b'n' | b's' => 2,
+            _ => 1,
+        };
+        let token = rest.get(..len).ok_or("Invalid hex direction")?;
+        res.push(token.parse()?);
+        rest = &rest[len..];
+    }
+    Ok(res)
+}
+
+impl Add<HexDelta> for Hex {
+    type Output = Hex;
+
+    fn add(self, rhs: HexDelta) -> Self::Output {
+        Hex(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl AddAssign<HexDelta> for Hex {
+    fn add_assign(&mut self, rhs: HexDelta) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<HexDelta> for Hex {
+    type Output = Hex;
+
+    fn sub(self, rhs: HexDelta) -> Self::Output {
+        Hex(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl SubAssign<HexDelta> for Hex {
+    fn sub_assign(&mut self, rhs: HexDelta) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub<Hex> for Hex {
+    type Output = HexDelta;
+
+    fn sub(self, rhs: Hex) -> Self::Output {
+        HexDelta(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl Add<HexDelta> for HexDelta {
+    type Output = HexDelta;
+
+    fn add(self, rhs: HexDelta) -> Self::Output {
+        HexDelta(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl Neg for HexDelta {
+    type Output = HexDelta;
+
+    fn neg(self) -> Self::Output {
+        HexDelta(-self.0, -self.1)
+    }
+}
+
+impl Mul<HexDelta> for isize {
+    type Output = HexDelta;
+
+    fn mul(self, rhs: HexDelta) -> Self::Output {
+        HexDelta(self * rhs.0, self * rhs.1)
+    }
+}
+
+impl Sum for HexDelta {
+    fn sum<I: Iterator<Item = HexDelta>>(iter: I) -> Self {
+        iter.fold(HexDelta(0, 0), |acc, d| acc + d)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn path_parsing() {
+        let path = parse_path("esenee").unwrap();
+        assert_eq!(
+            path,
+            [
+                PointyDir::East,
+                PointyDir::SouthEast,
+                PointyDir::NorthEast,
+                PointyDir::East
+            ]
+        );
+        assert_eq!(parse_path(""), Ok(vec![]));
+        assert!(parse_path("n").is_err());
+        assert!(parse_path("sx").is_err());
+        assert!(parse_path("s\u{e9}").is_err());
+
+        // nwwswee loops back to the start
+        let end: HexDelta = parse_path("nwwswee")
+            .unwrap()
+            .into_iter()
+            .map(HexDelta::from)
+            .sum();
+        assert_eq!(end, HexDelta(0, 0));
+    }
+
+    #[test]
+    fn directions_are_unit_steps() {
+        let pointy: HashSet<HexDelta> = PointyDir::ALL.iter().map(|&d| d.into()).collect();
+        let flat: HashSet<HexDelta> = FlatDir::ALL.iter().map(|&d| d.into()).collect();
+        assert_eq!(pointy, flat);
+        assert_eq!(pointy.len(), 6);
+        assert!(pointy.iter().all(|d| d.hex_len() == 1));
+    }
+
+    #[test]
+    fn neighbours() {
+        let h = Hex(-2, 7);
+        let neighbours: HashSet<Hex> = h.neighbours().collect();
+        assert_eq!(neighbours, h.ring(1).collect());
+        assert!(neighbours.iter().all(|n| n.distance(h) == 1));
+    }
+
+    #[test]
+    fn cube_conversion() {
+        let h = Hex(3, -5);
+        let c = Cube::from(h);
+        assert_eq!(c, Cube(3, -5, 2));
+        assert_eq!(Hex::try_from(c), Ok(h));
+        assert!(Hex::try_from(Cube(1, 1, 1)).is_err());
+        assert_eq!(
+            c.distance(Cube::from(Hex::origin())),
+            h.distance(Hex::origin())
+        );
+    }
+
+    #[test]
+    fn rings_and_spirals() {
+        let center = Hex(2, -1);
+        assert_eq!(center.ring(0).collect::<Vec<_>>(), [center]);
+        for radius in 1..5 {
+            let ring: HashSet<Hex> = center.ring(radius).collect();
+            assert_eq!(ring.len(), 6 * radius);
+            assert!(ring.iter().all(|h| h.distance(center) == radius as isize));
+        }
+
+        let spiral: Vec<Hex> = center.spiral(3).collect();
+        assert_eq!(spiral.len(), 1 + 6 + 12 + 18);
+        assert_eq!(spiral.iter().collect::<HashSet<_>>().len(), spiral.len());
+        assert!(spiral
+            .windows(2)
+            .all(|w| w[0].distance(center) <= w[1].distance(center)));
+    }
+
+    #[test]
+    fn rotation() {
+        let east = HexDelta::from(PointyDir::East);
+        assert_eq!(east.rotate(1), PointyDir::SouthEast.into());
+        assert_eq!(east.rotate(-1), PointyDir::NorthEast.into());
+        assert_eq!(east.rotate(3), -east);
+        assert_eq!(east.rotate(6), east);
+
+        let center = Hex(1, 1);
+        let h = Hex(4, -2);
+        assert_eq!(
+            h.rotate_around(center, 2).distance(center),
+            h.distance(center)
+        );
+        assert_eq!(h.rotate_around(center, 1).rotate_around(center, 5), h);
+    }
+
+    #[test]
+    fn pixels() {
+        let close = |a: (f64, f64), b: (f64, f64)| (a.0 - b.0).abs() + (a.1 - b.1).abs() < 1e-9;
+        let sqrt3 = 3f64.sqrt();
+        assert!(close(
+            Hex(1, 0).to_pixel(HexLayout::PointyTop, 1.0),
+            (sqrt3, 0.0)
+        ));
+        assert!(close(
+            Hex(0, 1).to_pixel(HexLayout::PointyTop, 2.0),
+            (sqrt3, 3.0)
+        ));
+        assert!(close(
+            Hex(1, 0).to_pixel(HexLayout::FlatTop, 1.0),
+            (1.5, sqrt3 / 2.0)
+        ));
+        assert!(close(
+            Hex(0, 1).to_pixel(HexLayout::FlatTop, 1.0),
+            (0.0, sqrt3)
+        ));
+    }
+}
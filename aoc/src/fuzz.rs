@@ -0,0 +1,117 @@
+use crate::rng::Rng;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+const SEED: u64 = 2020;
+const MUTATED_COUNT: usize = 300;
+const RANDOM_COUNT: usize = 100;
+const RANDOM_MAX_LEN: usize = 64;
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+// Characters mixed into every alphabet so that parsers also see separators,
+// signs, overly long numbers and multi-byte characters they don't expect.
+const EXTRA_CHARS: &str = "\n ,:-+()09xé\u{1f384}";
+
+fn alphabet(samples: &[&str]) -> Vec<char> {
+    let mut chars: Vec<char> = samples.iter().flat_map(|s| s.chars()).collect();
+    chars.extend(EXTRA_CHARS.chars());
+    chars.sort_unstable();
+    chars.dedup();
+    chars
+}
+
+pub fn random_input(rng: &mut Rng, alphabet: &[char], max_len: usize) -> String {
+    let len = rng.below(max_len + 1);
+    (0..len).map(|_| *rng.choose(alphabet)).collect()
+}
+
+pub fn mutate(rng: &mut Rng, s: &str, alphabet: &[char]) -> String {
+    let mut chars: Vec<char> = s.chars().collect();
+
+    for _ in 0..1 + rng.below(4) {
+        let len = chars.len();
+        let pos = rng.below(len + 1);
+        match rng.below(7) {
+            0 if pos < len => {
+                chars.remove(pos);
+            }
+            1 => chars.insert(pos, *rng.choose(alphabet)),
+            2 if pos < len => chars[pos] = *rng.choose(alphabet),
+            3 => chars.truncate(pos),
+            4 => {
+                let end = pos + rng.below(len - pos + 1);
+                let span: Vec<char> = chars[pos..end].to_vec();
+                let at = rng.below(len + 1);
+                chars.splice(at..at, span);
+            }
+            5 => {
+                let digits = "9".repeat(1 + rng.below(40));
+                chars.splice(pos..pos, digits.chars());
+            }
+            _ => {
+                let mut lines: Vec<String> = chars
+                    .split(|&c| c == '\n')
+                    .map(|l| l.iter().collect())
+                    .collect();
+                let i = rng.below(lines.len());
+                lines.remove(i);
+                chars = lines.join("\n").chars().collect();
+            }
+        }
+    }
+
+    chars.into_iter().collect()
+}
+
+fn run_with_timeout<T, E, F>(parse: F, input: String) -> bool
+where
+    F: Fn(&str) -> Result<T, E> + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    let handle = thread::spawn({
+        let input = input.clone();
+        move || {
+            let ok = parse(&input).is_ok();
+            let _ = tx.send(ok);
+        }
+    });
+
+    match rx.recv_timeout(TIMEOUT) {
+        Ok(ok) => {
+            handle.join().unwrap();
+            ok
+        }
+        Err(mpsc::RecvTimeoutError::Timeout) => panic!("Parser hung on input {:?}", input),
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            panic!("Parser panicked on input {:?}", input)
+        }
+    }
+}
+
+// Checks that every sample parses, then feeds mutations of the samples and
+// random strings over their alphabet to `parse`, failing if it panics or does
+// not return in time. Returning `Ok` is acceptable for those since a mutation
+// may well be valid input.
+pub fn check_parser<T, E, F>(samples: &[&str], parse: F)
+where
+    F: Fn(&str) -> Result<T, E> + Copy + Send + 'static,
+{
+    let mut rng = Rng::new(SEED);
+    let alphabet = alphabet(samples);
+
+    for sample in samples {
+        assert!(
+            run_with_timeout(parse, sample.to_string()),
+            "Sample failed to parse: {:?}",
+            sample
+        );
+        for _ in 0..MUTATED_COUNT {
+            run_with_timeout(parse, mutate(&mut rng, sample, &alphabet));
+        }
+    }
+
+    for _ in 0..RANDOM_COUNT {
+        run_with_timeout(parse, random_input(&mut rng, &alphabet, RANDOM_MAX_LEN));
+    }
+}
@@ -0,0 +1,1058 @@
+mod search;
+pub use search::Paths;
+
+use crate::coordinates::{Coord, Delta, Rect};
+use crate::symmetry::D4;
+use std::convert::{TryFrom, TryInto};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Rotation {
+    Cw0,
+    Cw90,
+    Cw180,
+    Cw270,
+}
+
+impl Rotation {
+    pub fn to_cw_count(&self) -> usize {
+        match *self {
+            Rotation::Cw0 => 0,
+            Rotation::Cw90 => 1,
+            Rotation::Cw180 => 2,
+            Rotation::Cw270 => 3,
+        }
+    }
+}
+
+// How coordinates outside the grid are resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Topology {
+    // Nothing outside the grid
+    #[default]
+    Bounded,
+    // Columns repeat to the left and right
+    WrapHorizontal,
+    // Rows repeat above and below
+    WrapVertical,
+    // Both, so the grid tiles the whole plane
+    Torus,
+    // Snaps to the nearest edge cell
+    Clamped,
+}
+
+impl Topology {
+    // The equivalent topology after swapping the axes
+    fn transposed(self) -> Topology {
+        match self {
+            Topology::WrapHorizontal => Topology::WrapVertical,
+            Topology::WrapVertical => Topology::WrapHorizontal,
+            t => t,
+        }
+    }
+}
+
+// Which cells count as adjacent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    // Orthogonal steps only
+    VonNeumann,
+    // Orthogonal and diagonal steps
+    Moore,
+}
+
+impl Neighbourhood {
+    pub fn deltas(self) -> &'static [Delta] {
+        match self {
+            Neighbourhood::VonNeumann => &Delta::VON_NEUMANN,
+            Neighbourhood::Moore => &Delta::MOORE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    grid: Vec<T>,
+    width: usize,
+    topology: Topology,
+}
+
+impl<T> Grid<T> {
+    fn from_parts(grid: Vec<T>, width: usize) -> Grid<T> {
+        Grid {
+            grid,
+            width,
+            topology: Topology::Bounded,
+        }
+    }
+
+    pub fn with_topology(mut self, topology: Topology) -> Grid<T> {
+        self.topology = topology;
+        self
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+    }
+
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Grid<T>
+    where
+        F: FnMut(Coord) -> T,
+    {
+        let grid = (0..height as isize)
+            .flat_map(|y| (0..width as isize).map(move |x| Coord(x, y)))
+            .map(&mut f)
+            .collect();
+        Grid::from_parts(grid, width)
+    }
+
+    // Takes cells in row-major order
+    pub fn from_vec(width: usize, vec: Vec<T>) -> Result<Grid<T>, &'static str> {
+        if !vec.len().is_multiple_of(width) {
+            return Err("Length not a multiple of width");
+        }
+        Ok(Grid::from_parts(vec, width))
+    }
+
+    // A grid without columns has no rows either
+    pub fn height(&self) -> usize {
+        if self.width == 0 {
+            return 0;
+        }
+        self.grid.len() / self.width
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.grid.iter()
+    }
+
+    // Every coordinate in row-major order
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let (w, h) = (self.width() as isize, self.height() as isize);
+        (0..h).flat_map(move |y| (0..w).map(move |x| Coord(x, y)))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks() rejects 0, but a zero-width grid has no cells anyway
+        self.grid.chunks(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    // Panics if `y` is out of range
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height(), "Row {} is outside the grid", y);
+        &self.grid[y * self.width..(y + 1) * self.width]
+    }
+
+    // Top to bottom. Panics if `x` is out of range
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {} is outside the grid", x);
+        self.grid[x..].iter().step_by(self.width)
+    }
+
+    // Borders run left to right or top to bottom, like rows and columns.
+    // An empty grid has empty borders.
+    pub fn top_border(&self) -> impl Iterator<Item = &T> {
+        self.rows().next().unwrap_or(&[]).iter()
+    }
+
+    pub fn bottom_border(&self) -> impl Iterator<Item = &T> {
+        self.rows().last().unwrap_or(&[]).iter()
+    }
+
+    pub fn left_border(&self) -> impl Iterator<Item = &T> {
+        self.grid.iter().step_by(self.width.max(1))
+    }
+
+    pub fn right_border(&self) -> impl Iterator<Item = &T> {
+        let skip = self.width.saturating_sub(1);
+        self.grid.iter().skip(skip).step_by(self.width.max(1))
+    }
+
+    // Maps `c` onto the cell it refers to under the grid's topology, if any
+    pub fn resolve(&self, c: Coord) -> Option<Coord> {
+        let (w, h) = (self.width() as isize, self.height() as isize);
+        if w == 0 || h == 0 {
+            return None;
+        }
+
+        let wrap_x = |x: isize| x.rem_euclid(w);
+        let wrap_y = |y: isize| y.rem_euclid(h);
+        let Coord(x, y) = c;
+        let (x, y) = match self.topology {
+            Topology::Bounded => (x, y),
+            Topology::WrapHorizontal => (wrap_x(x), y),
+            Topology::WrapVertical => (x, wrap_y(y)),
+            Topology::Torus => (wrap_x(x), wrap_y(y)),
+            Topology::Clamped => (x.clamp(0, w - 1), y.clamp(0, h - 1)),
+        };
+
+        if (0..w).contains(&x) && (0..h).contains(&y) {
+            Some(Coord(x, y))
+        } else {
+            None
+        }
+    }
+
+    // Like `resolve`, but only wrapping moves a coordinate. Used where
+    // clamping would make an edge cell stand in for cells that don't exist.
+    fn wrap(&self, c: Coord) -> Option<Coord> {
+        let resolved = self.resolve(c)?;
+        if self.topology == Topology::Clamped && resolved != c {
+            None
+        } else {
+            Some(resolved)
+        }
+    }
+
+    fn index_for(&self, c: Coord) -> Option<usize> {
+        let Coord(x, y) = self.resolve(c)?;
+        Some(x as usize + self.width() * y as usize)
+    }
+
+    // Distinct neighbours of `c` under the grid's topology. Steps that land
+    // back on `c` or on an earlier neighbour, as clamping or wrapping a
+    // narrow grid can do, are skipped.
+    pub fn neighbours(
+        &self,
+        c: Coord,
+        neighbourhood: Neighbourhood,
+    ) -> impl Iterator<Item = Coord> + '_ {
+        let deltas = neighbourhood.deltas();
+        let centre = self.resolve(c);
+        deltas.iter().enumerate().filter_map(move |(i, &d)| {
+            let n = self.resolve(c + d)?;
+            let repeated = deltas[..i].iter().any(|&e| self.resolve(c + e) == Some(n));
+            if Some(n) == centre || repeated {
+                None
+            } else {
+                Some(n)
+            }
+        })
+    }
+
+    pub fn von_neumann(&self, c: Coord) -> impl Iterator<Item = Coord> + '_ {
+        self.neighbours(c, Neighbourhood::VonNeumann)
+    }
+
+    pub fn moore(&self, c: Coord) -> impl Iterator<Item = Coord> + '_ {
+        self.neighbours(c, Neighbourhood::Moore)
+    }
+
+    pub fn get(&self, c: Coord) -> Option<&T> {
+        let idx = self.index_for(c)?;
+        self.grid.get(idx)
+    }
+
+    pub fn get_mut(&mut self, c: Coord) -> Option<&mut T> {
+        let idx = self.index_for(c)?;
+        self.grid.get_mut(idx)
+    }
+
+    pub fn view(&self) -> GridView<'_, T> {
+        GridView {
+            grid: self,
+            origin: Coord::origin(),
+            source_width: self.width(),
+            source_height: self.height(),
+            transform: D4::IDENTITY,
+        }
+    }
+
+    fn coord_for(&self, idx: usize) -> Coord {
+        Coord((idx % self.width) as isize, (idx / self.width) as isize)
+    }
+
+    // Cells in row-major order, with their coordinates
+    pub fn enumerate(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.grid
+            .iter()
+            .enumerate()
+            .map(move |(i, e)| (self.coord_for(i), e))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            grid: self.grid.iter().map(f).collect(),
+            width: self.width,
+            topology: self.topology,
+        }
+    }
+
+    // Combines cells at the same coordinates. Panics if the sizes differ.
+    pub fn zip_with<U, V, F>(&self, other: &Grid<U>, mut f: F) -> Grid<V>
+    where
+        F: FnMut(&T, &U) -> V,
+    {
+        assert_eq!(
+            (self.width(), self.height()),
+            (other.width(), other.height()),
+            "Grid sizes differ"
+        );
+        Grid {
+            grid: self
+                .grid
+                .iter()
+                .zip(other.grid.iter())
+                .map(|(a, b)| f(a, b))
+                .collect(),
+            width: self.width,
+            topology: self.topology,
+        }
+    }
+
+    // Which cells are covered by at least one of `matches`
+    pub fn mark_matches(&self, matches: &[PatternMatch]) -> Grid<bool> {
+        let mut marks = self.map(|_| false);
+        for &c in matches.iter().flat_map(|m| m.cells.iter()) {
+            marks[c] = true;
+        }
+        marks
+    }
+
+    pub fn flip_inplace(&mut self, axis: Axis) {
+        let w = self.width() as isize;
+        let h = self.height() as isize;
+        match axis {
+            Axis::Horizontal => {
+                for y in 0..h / 2 {
+                    for x in 0..w {
+                        let i1 = self.index_for(Coord(x, y)).unwrap();
+                        let i2 = self.index_for(Coord(x, h - 1 - y)).unwrap();
+                        self.grid.swap(i1, i2);
+                    }
+                }
+            }
+            Axis::Vertical => {
+                for x in 0..w / 2 {
+                    for y in 0..h {
+                        let i1 = self.index_for(Coord(x, y)).unwrap();
+                        let i2 = self.index_for(Coord(w - 1 - x, y)).unwrap();
+                        self.grid.swap(i1, i2);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// A placement of a pattern found by `Grid::find_pattern`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternMatch {
+    // Applied to the pattern before placing it
+    pub orientation: D4,
+    // Where the oriented pattern's top left corner lands
+    pub at: Coord,
+    // The grid cells matched by the pattern's non-wildcard cells
+    pub cells: Vec<Coord>,
+}
+
+impl<T> Grid<T>
+where
+    T: std::cmp::PartialEq,
+{
+    pub fn count_eq(&self, item: &T) -> usize {
+        self.grid.iter().filter(|&e| e == item).count()
+    }
+
+    // Every placement of `pattern`, in any of its eight orientations, where
+    // all cells other than `wildcard` match. Matches may overlap. Orientations
+    // of a symmetric pattern that look the same are only searched once.
+    // Patterns may wrap around a wrapping grid, but a clamped grid is
+    // treated as bounded.
+    pub fn find_pattern(&self, pattern: &Grid<T>, wildcard: &T) -> Vec<PatternMatch> {
+        let wraps = self.topology != Topology::Bounded && self.topology != Topology::Clamped;
+        let mut searched: Vec<GridView<'_, T>> = Vec::new();
+        let mut matches = Vec::new();
+
+        for &t in &D4::ALL {
+            let view = pattern.view().transformed(t);
+            if searched.contains(&view) {
+                continue;
+            }
+            let cells: Vec<_> = view.enumerate().filter(|(_, e)| *e != wildcard).collect();
+
+            for at in self.coords() {
+                let offset = at - Coord::origin();
+                let found = cells.iter().all(|&(c, e)| match self.wrap(c + offset) {
+                    None => false,
+                    Some(p) => &self[p] == e,
+                });
+                if !found {
+                    continue;
+                }
+
+                let placed: Vec<_> = cells
+                    .iter()
+                    .map(|&(c, _)| self.wrap(c + offset).unwrap())
+                    .collect();
+                // A pattern bigger than a wrapping grid can land on itself
+                if wraps {
+                    let mut distinct = placed.clone();
+                    distinct.sort();
+                    distinct.dedup();
+                    if distinct.len() != placed.len() {
+                        continue;
+                    }
+                }
+                matches.push(PatternMatch {
+                    orientation: t,
+                    at,
+                    cells: placed,
+                });
+            }
+            searched.push(view);
+        }
+
+        matches
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        Grid::from_parts(vec![fill; width * height], width)
+    }
+
+    // A copy of the cells inside `rect`, if it lies within the grid
+    pub fn subgrid(&self, rect: Rect) -> Option<Grid<T>> {
+        Some(self.view().subview(rect)?.to_owned())
+    }
+
+    // Strips `n` cells from every edge. Returns None if nothing would remain.
+    pub fn crop_border(&self, n: usize) -> Option<Grid<T>> {
+        let (w, h) = (self.width(), self.height());
+        if 2 * n >= w || 2 * n >= h {
+            return None;
+        }
+        let n = n as isize;
+        let max = Coord(w as isize - 1 - n, h as isize - 1 - n);
+        self.subgrid(Rect::new(Coord(n, n), max))
+    }
+
+    // Copies `other` so that its top left cell lands on `at`. Cells falling
+    // off the grid wrap around if it wraps, and are skipped otherwise.
+    pub fn blit(&mut self, other: &Grid<T>, at: Coord) {
+        for (c, e) in other.enumerate() {
+            if let Some(p) = self.wrap(at + (c - Coord::origin())) {
+                self[p] = e.clone();
+            }
+        }
+    }
+
+    // Stitches equally sized tiles together, laid out as in `tiles`
+    pub fn from_tiles(tiles: Grid<Grid<T>>) -> Result<Grid<T>, &'static str> {
+        let (tw, th) = match tiles.iter().next() {
+            None => return Grid::from_vec(0, vec![]),
+            Some(t) => (t.width(), t.height()),
+        };
+        if tiles.iter().any(|t| (t.width(), t.height()) != (tw, th)) {
+            return Err("Tiles differ in size");
+        }
+
+        let (w, h) = (tw * tiles.width(), th * tiles.height());
+        let (tw, th) = (tw as isize, th as isize);
+        Ok(Grid::from_fn(w, h, |c| {
+            tiles[Coord(c.0 / tw, c.1 / th)][Coord(c.0 % tw, c.1 % th)].clone()
+        }))
+    }
+
+    // Overwrites every cell covered by `matches`
+    pub fn replace_matches(&mut self, matches: &[PatternMatch], with: T) {
+        for &c in matches.iter().flat_map(|m| m.cells.iter()) {
+            self[c] = with.clone();
+        }
+    }
+
+    // Mirrors across the main diagonal, so rows become columns
+    pub fn transpose(&self) -> Grid<T> {
+        let g = Grid::from_fn(self.height(), self.width(), |c| {
+            self[Coord(c.1, c.0)].clone()
+        });
+        g.with_topology(self.topology.transposed())
+    }
+
+    // Builds the transformed grid in one pass, pulling each cell from its
+    // preimage. Accepts anything convertible to `D4`, like a `Rotation`.
+    pub fn rotated<R: Into<D4>>(&self, t: R) -> Grid<T> {
+        let t = t.into();
+        let g = self.view().transformed(t).to_owned();
+        if t.cw_count() % 2 == 1 {
+            g.with_topology(self.topology.transposed())
+        } else {
+            g.with_topology(self.topology)
+        }
+    }
+
+    pub fn flipped(&self, axis: Axis) -> Grid<T> {
+        self.rotated(axis)
+    }
+
+    // All eight orientations, in the order of `D4::ALL`
+    pub fn symmetries(&self) -> impl Iterator<Item = (D4, Grid<T>)> + '_ {
+        D4::ALL.iter().map(move |&t| (t, self.rotated(t)))
+    }
+
+    pub fn rotate_clockwise_inplace(&mut self) {
+        self.transform_inplace(D4::rotate_cw(1));
+    }
+
+    pub fn rotate_inplace(&mut self, rotation: Rotation) {
+        self.transform_inplace(rotation.into());
+    }
+
+    pub fn transform_inplace(&mut self, t: D4) {
+        if t != D4::IDENTITY {
+            *self = self.rotated(t);
+        }
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, c: Coord) -> &Self::Output {
+        match self.get(c) {
+            Some(e) => e,
+            None => panic!("{:?} is outside the grid", c),
+        }
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, c: Coord) -> &mut Self::Output {
+        match self.get_mut(c) {
+            Some(e) => e,
+            None => panic!("{:?} is outside the grid", c),
+        }
+    }
+}
+
+impl<T> FromStr for Grid<T>
+where
+    T: TryFrom<char>,
+{
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut grid: Vec<T> = Vec::new();
+        let mut width: Option<usize> = None;
+
+        for line in s.lines() {
+            if line.is_empty() {
+                return Err("Empty line");
+            }
+
+            let mut cur_width = 0usize;
+            for c in line.chars() {
+                let item: T = c.try_into().or(Err("Failed parsing char"))?;
+                grid.push(item);
+                cur_width += 1;
+            }
+
+            if width.is_some() && width.unwrap() != cur_width {
+                return Err("Non-uniform line length");
+            }
+
+            width = Some(cur_width);
+        }
+
+        let width = width.ok_or("No lines")?;
+        Ok(Grid::from_parts(grid, width))
+    }
+}
+
+impl<T> fmt::Display for Grid<T>
+where
+    T: Clone,
+    char: From<T>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for e in row {
+                write!(f, "{}", char::from(e.clone()))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// A window onto a grid seen through a symmetry. Lookups are mapped back to
+// the underlying grid, so nothing is copied until `to_owned`.
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    // The window in grid coordinates, before the transform is applied
+    origin: Coord,
+    source_width: usize,
+    source_height: usize,
+    transform: D4,
+}
+
+// Derived impls would needlessly require `T: Clone`
+impl<'a, T> Clone for GridView<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for GridView<'a, T> {}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn width(&self) -> usize {
+        self.transform.dims(self.source_width, self.source_height).0
+    }
+
+    pub fn height(&self) -> usize {
+        self.transform.dims(self.source_width, self.source_height).1
+    }
+
+    // Applies `t` on top of the view's current transform
+    pub fn transformed(self, t: D4) -> GridView<'a, T> {
+        GridView {
+            transform: self.transform.then(t),
+            ..self
+        }
+    }
+
+    // Narrows the view to `rect`, given in view coordinates. Returns None
+    // unless `rect` lies entirely within the view.
+    pub fn subview(self, rect: Rect) -> Option<GridView<'a, T>> {
+        let (w, h) = (self.width() as isize, self.height() as isize);
+        if rect.min.0 < 0 || rect.min.1 < 0 || rect.max.0 >= w || rect.max.1 >= h {
+            return None;
+        }
+
+        let inverse = self.transform.inverse();
+        let corners = rect.corners();
+        let local = corners
+            .iter()
+            .map(|&c| inverse.apply_coord(c, w as usize, h as usize));
+        let local = Rect::from_points(local)?;
+        Some(GridView {
+            origin: self.origin + (local.min - Coord::origin()),
+            source_width: local.width(),
+            source_height: local.height(),
+            ..self
+        })
+    }
+
+    // Where a view coordinate lives in the underlying grid
+    pub fn source_coord(&self, c: Coord) -> Option<Coord> {
+        let (w, h) = (self.width(), self.height());
+        if c.0 < 0 || c.1 < 0 || c.0 as usize >= w || c.1 as usize >= h {
+            return None;
+        }
+        let local = self.transform.inverse().apply_coord(c, w, h);
+        Some(self.origin + (local - Coord::origin()))
+    }
+
+    pub fn get(&self, c: Coord) -> Option<&'a T> {
+        self.grid.get(self.source_coord(c)?)
+    }
+
+    // Every view coordinate in row-major order
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let (w, h) = (self.width() as isize, self.height() as isize);
+        (0..h).flat_map(move |y| (0..w).map(move |x| Coord(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> {
+        self.enumerate().map(|(_, e)| e)
+    }
+
+    pub fn enumerate(&self) -> impl Iterator<Item = (Coord, &'a T)> {
+        let view = *self;
+        self.coords().map(move |c| (c, view.get(c).unwrap()))
+    }
+}
+
+impl<'a, T> GridView<'a, T>
+where
+    T: Clone,
+{
+    // Copies the visible cells into a new, bounded grid
+    pub fn to_owned(&self) -> Grid<T> {
+        Grid::from_fn(self.width(), self.height(), |c| self[c].clone())
+    }
+}
+
+// Views are equal when they show the same cells, wherever they come from
+impl<'a, T> PartialEq for GridView<'a, T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        (self.width(), self.height()) == (other.width(), other.height())
+            && self.iter().eq(other.iter())
+    }
+}
+
+impl<'a, T> Index<Coord> for GridView<'a, T> {
+    type Output = T;
+
+    fn index(&self, c: Coord) -> &Self::Output {
+        match self.get(c) {
+            Some(e) => e,
+            None => panic!("{:?} is outside the view", c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<char> {
+        "abc\ndef\n".parse().unwrap()
+    }
+
+    #[test]
+    fn constructors() {
+        let g = Grid::new(3, 2, '.');
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g.count_eq(&'.'), 6);
+
+        let g = Grid::from_fn(3, 2, |c| (b'a' + (c.0 + 3 * c.1) as u8) as char);
+        assert_eq!(g, sample());
+
+        let g = Grid::from_vec(3, "abcdef".chars().collect()).unwrap();
+        assert_eq!(g, sample());
+        assert!(Grid::from_vec(4, "abcdef".chars().collect()).is_err());
+        assert!(Grid::from_vec(0, vec!['a']).is_err());
+
+        let empty: Grid<char> = Grid::from_vec(0, vec![]).unwrap();
+        assert_eq!((empty.width(), empty.height()), (0, 0));
+        assert_eq!(Grid::from_fn(0, 5, |_| 0).height(), 0);
+    }
+
+    #[test]
+    fn indexing() {
+        let mut g = sample();
+        assert_eq!(g[Coord(2, 1)], 'f');
+        g[Coord(0, 1)] = 'x';
+        assert_eq!(g.to_string(), "abc\nxef\n");
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let _ = sample()[Coord(3, 0)];
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let g = sample();
+        let rows: Vec<String> = g.rows().map(|r| r.iter().collect()).collect();
+        assert_eq!(rows, ["abc", "def"]);
+        let columns: Vec<String> = g.columns().map(|c| c.collect()).collect();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+        assert_eq!(g.row(1), ['d', 'e', 'f']);
+        assert_eq!(g.column(2).collect::<String>(), "cf");
+
+        let coords: Vec<_> = g.coords().collect();
+        assert_eq!(coords.len(), 6);
+        assert_eq!(coords[3], Coord(0, 1));
+        assert!(g.coords().zip(g.iter()).all(|(c, e)| g[c] == *e));
+
+        let empty: Grid<char> = Grid::from_vec(0, vec![]).unwrap();
+        assert_eq!(empty.rows().count(), 0);
+        assert_eq!(empty.columns().count(), 0);
+        assert_eq!(empty.coords().count(), 0);
+    }
+
+    #[test]
+    fn borders() {
+        let g: Grid<char> = "abc\ndef\nghi\n".parse().unwrap();
+        assert_eq!(g.top_border().collect::<String>(), "abc");
+        assert_eq!(g.bottom_border().collect::<String>(), "ghi");
+        assert_eq!(g.left_border().collect::<String>(), "adg");
+        assert_eq!(g.right_border().collect::<String>(), "cfi");
+
+        let g = sample();
+        assert_eq!(g.bottom_border().collect::<String>(), "def");
+        assert_eq!(g.right_border().collect::<String>(), "cf");
+
+        let no_columns: Grid<char> = Grid::from_vec(0, vec![]).unwrap();
+        let no_rows: Grid<char> = Grid::from_vec(3, vec![]).unwrap();
+        for g in &[no_columns, no_rows] {
+            assert_eq!(g.top_border().count(), 0);
+            assert_eq!(g.bottom_border().count(), 0);
+            assert_eq!(g.left_border().count(), 0);
+            assert_eq!(g.right_border().count(), 0);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn row_out_of_bounds() {
+        sample().row(2);
+    }
+
+    #[test]
+    fn topologies() {
+        let g = sample();
+        assert_eq!(g.get(Coord(-1, 0)), None);
+        assert_eq!(g.von_neumann(Coord(0, 0)).count(), 2);
+        assert_eq!(g.moore(Coord(1, 0)).count(), 5);
+
+        let g = sample().with_topology(Topology::WrapHorizontal);
+        assert_eq!(g[Coord(-1, 0)], 'c');
+        assert_eq!(g[Coord(7, 1)], 'e');
+        assert_eq!(g.get(Coord(0, 2)), None);
+        assert_eq!(g.resolve(Coord(-4, 1)), Some(Coord(2, 1)));
+
+        let g = sample().with_topology(Topology::WrapVertical);
+        assert_eq!(g[Coord(0, -1)], 'd');
+        assert_eq!(g.get(Coord(3, 0)), None);
+
+        let mut g = sample().with_topology(Topology::Torus);
+        assert_eq!(g[Coord(-1, -1)], 'f');
+        g[Coord(3, 2)] = 'x';
+        assert_eq!(g[Coord(0, 0)], 'x');
+        // Only two rows, so the cells above and below coincide
+        let neighbours: Vec<_> = g.moore(Coord(0, 0)).collect();
+        assert_eq!(
+            neighbours,
+            [
+                Coord(2, 1),
+                Coord(0, 1),
+                Coord(1, 1),
+                Coord(1, 0),
+                Coord(2, 0)
+            ]
+        );
+
+        let g = sample().with_topology(Topology::Clamped);
+        assert_eq!(g[Coord(-5, 10)], 'd');
+        assert_eq!(g[Coord(10, -5)], 'c');
+        let neighbours: Vec<_> = g.von_neumann(Coord(0, 0)).collect();
+        assert_eq!(neighbours, [Coord(1, 0), Coord(0, 1)]);
+        let neighbours: Vec<_> = g.moore(Coord(0, 0)).collect();
+        assert_eq!(neighbours, [Coord(1, 0), Coord(1, 1), Coord(0, 1)]);
+
+        let mut g = sample().with_topology(Topology::WrapHorizontal);
+        g.rotate_clockwise_inplace();
+        assert_eq!(g.topology(), Topology::WrapVertical);
+
+        let empty = Grid::<char>::from_vec(0, vec![]).unwrap();
+        assert_eq!(empty.with_topology(Topology::Torus).get(Coord(0, 0)), None);
+    }
+
+    #[test]
+    fn transformations() {
+        let g = sample();
+        assert_eq!(g.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(g.rotated(Rotation::Cw90).to_string(), "da\neb\nfc\n");
+        assert_eq!(g.rotated(Rotation::Cw180).to_string(), "fed\ncba\n");
+        assert_eq!(g.rotated(D4::rotate_cw(3)).to_string(), "cf\nbe\nad\n");
+        assert_eq!(g.flipped(Axis::Horizontal).to_string(), "def\nabc\n");
+        assert_eq!(g.flipped(Axis::Vertical).to_string(), "cba\nfed\n");
+
+        let wrapped = g.clone().with_topology(Topology::WrapHorizontal);
+        assert_eq!(wrapped.transpose().topology(), Topology::WrapVertical);
+        assert_eq!(
+            wrapped.rotated(Rotation::Cw180).topology(),
+            Topology::WrapHorizontal
+        );
+
+        // In the order of D4::ALL: rotations, then flipped rotations
+        let expected = [
+            "abc\ndef\n",
+            "da\neb\nfc\n",
+            "fed\ncba\n",
+            "cf\nbe\nad\n",
+            "def\nabc\n",
+            "ad\nbe\ncf\n",
+            "cba\nfed\n",
+            "fc\neb\nda\n",
+        ];
+        let variants: Vec<_> = g.symmetries().collect();
+        assert_eq!(variants.len(), 8);
+        for ((t, v), &s) in variants.into_iter().zip(expected.iter()) {
+            assert_eq!(v.to_string(), s);
+            let mut in_place = g.clone();
+            in_place.transform_inplace(t);
+            assert_eq!(in_place.to_string(), s);
+        }
+
+        let mut in_place = g.clone();
+        in_place.rotate_clockwise_inplace();
+        assert_eq!(in_place.to_string(), expected[1]);
+        in_place.rotate_inplace(Rotation::Cw270);
+        assert_eq!(in_place, g);
+    }
+
+    #[test]
+    fn views() {
+        let g: Grid<char> = "abcd\nefgh\nijkl\n".parse().unwrap();
+        let view = g.view();
+        assert_eq!(view.to_owned(), g);
+        assert_eq!(view.iter().collect::<String>(), "abcdefghijkl");
+
+        let expected = [
+            "abcd\nefgh\nijkl\n",
+            "iea\njfb\nkgc\nlhd\n",
+            "lkji\nhgfe\ndcba\n",
+            "dhl\ncgk\nbfj\naei\n",
+            "ijkl\nefgh\nabcd\n",
+            "aei\nbfj\ncgk\ndhl\n",
+            "dcba\nhgfe\nlkji\n",
+            "lhd\nkgc\njfb\niea\n",
+        ];
+        for (&t, &s) in D4::ALL.iter().zip(expected.iter()) {
+            let transformed = view.transformed(t);
+            assert_eq!(transformed.to_owned().to_string(), s);
+            for (c, e) in transformed.enumerate() {
+                assert_eq!(g[transformed.source_coord(c).unwrap()], *e);
+            }
+        }
+
+        let rotated = view.transformed(D4::rotate_cw(1));
+        assert_eq!((rotated.width(), rotated.height()), (3, 4));
+        assert_eq!(rotated[Coord(0, 0)], 'i');
+        assert_eq!(rotated.get(Coord(3, 0)), None);
+        assert_eq!(rotated.get(Coord(-1, 0)), None);
+
+        // A window of the rotated view, then rotated back
+        let window = rotated
+            .subview(Rect::new(Coord(1, 1), Coord(2, 2)))
+            .unwrap();
+        assert_eq!(window.to_owned().to_string(), "fb\ngc\n");
+        assert_eq!(window.source_coord(Coord(0, 0)), Some(Coord(1, 1)));
+        let back = window.transformed(D4::rotate_cw(3));
+        assert_eq!(back.to_owned().to_string(), "bc\nfg\n");
+
+        assert!(rotated
+            .subview(Rect::new(Coord(0, 0), Coord(3, 0)))
+            .is_none());
+        assert!(view.subview(Rect::new(Coord(-1, 0), Coord(0, 0))).is_none());
+
+        // Views compare by content, not by how they were built
+        let symmetric: Grid<char> = "ab\nba\n".parse().unwrap();
+        let view = symmetric.view();
+        assert!(view == view.transformed(D4::rotate_cw(2)));
+        assert!(view != view.transformed(D4::rotate_cw(1)));
+    }
+
+    #[test]
+    fn slicing_and_stitching() {
+        let g: Grid<char> = "abcd\nefgh\nijkl\n".parse().unwrap();
+        let sub = g.subgrid(Rect::new(Coord(1, 0), Coord(2, 1))).unwrap();
+        assert_eq!(sub.to_string(), "bc\nfg\n");
+        assert!(g.subgrid(Rect::new(Coord(2, 2), Coord(4, 2))).is_none());
+
+        assert_eq!(g.crop_border(0), Some(g.clone()));
+        assert_eq!(g.crop_border(1).unwrap().to_string(), "fg\n");
+        assert!(g.crop_border(2).is_none());
+
+        let mut canvas = Grid::new(4, 3, '.');
+        canvas.blit(&sub, Coord(3, 2));
+        assert_eq!(canvas.to_string(), "....\n....\n...b\n");
+        let mut canvas = canvas.with_topology(Topology::Torus);
+        canvas.blit(&sub, Coord(3, 2));
+        assert_eq!(canvas.to_string(), "g..f\n....\nc..b\n");
+        let mut canvas = Grid::new(4, 3, '.').with_topology(Topology::Clamped);
+        canvas.blit(&sub, Coord(3, 2));
+        assert_eq!(canvas.to_string(), "....\n....\n...b\n");
+
+        let tiles = Grid::from_fn(2, 2, |c| {
+            let ch = (b'w' + (c.0 + 2 * c.1) as u8) as char;
+            Grid::from_fn(2, 1, |_| ch)
+        });
+        let stitched = Grid::from_tiles(tiles).unwrap();
+        assert_eq!(stitched.to_string(), "wwxx\nyyzz\n");
+
+        let uneven = Grid::from_vec(2, vec![sub.clone(), g.clone()]).unwrap();
+        assert!(Grid::from_tiles(uneven).is_err());
+        let none: Grid<Grid<char>> = Grid::from_vec(0, vec![]).unwrap();
+        assert_eq!(Grid::from_tiles(none).unwrap().width(), 0);
+    }
+
+    #[test]
+    fn patterns() {
+        let mut g: Grid<char> = "#..#\n##.#\n.###\n".parse().unwrap();
+        let pattern: Grid<char> = "#?\n##\n".parse().unwrap();
+
+        // The pattern is its own mirror image, so flips find nothing new
+        let matches = g.find_pattern(&pattern, &'?');
+        let found: Vec<_> = matches.iter().map(|m| (m.orientation, m.at)).collect();
+        assert_eq!(
+            found,
+            [
+                (D4::IDENTITY, Coord(0, 0)),
+                (D4::IDENTITY, Coord(1, 1)),
+                (D4::rotate_cw(2), Coord(0, 1)),
+                (D4::rotate_cw(3), Coord(2, 1)),
+            ]
+        );
+        assert_eq!(matches[0].cells, [Coord(0, 0), Coord(0, 1), Coord(1, 1)]);
+
+        // Overlapping matches share cells
+        let marks = g.mark_matches(&matches);
+        assert_eq!(marks.count_eq(&true), 7);
+        let bar: Grid<char> = "##\n".parse().unwrap();
+        assert_eq!(g.find_pattern(&bar, &'?').len(), 7);
+        g.replace_matches(&matches, 'O');
+        assert_eq!(g.to_string(), "O..#\nOO.O\n.OOO\n");
+
+        // Matches can span the edges of a wrapping grid
+        let g: Grid<char> = "#.#\n".parse().unwrap();
+        assert!(g.find_pattern(&bar, &'?').is_empty());
+        let g = g.with_topology(Topology::WrapHorizontal);
+        let matches = g.find_pattern(&bar, &'?');
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].cells, [Coord(2, 0), Coord(0, 0)]);
+
+        // Clamping doesn't repeat edge cells to complete a match
+        let g: Grid<char> = "#..\n".parse().unwrap();
+        let g = g.with_topology(Topology::Clamped);
+        assert!(g.find_pattern(&bar, &'?').is_empty());
+
+        // Nor can a match use the same cell twice by wrapping onto itself
+        let g: Grid<char> = "#\n".parse().unwrap();
+        let g = g.with_topology(Topology::Torus);
+        assert!(g.find_pattern(&bar, &'?').is_empty());
+    }
+
+    #[test]
+    fn mapping() {
+        let g = sample();
+        let upper = g.map(|c| c.to_ascii_uppercase());
+        assert_eq!(upper.to_string(), "ABC\nDEF\n");
+
+        let indices = Grid::from_fn(3, 2, |c| c.0 + c.1);
+        let zipped = g.zip_with(&indices, |&ch, &i| if i % 2 == 0 { ch } else { '.' });
+        assert_eq!(zipped.to_string(), "a.c\n.e.\n");
+
+        let cells: Vec<_> = g.enumerate().collect();
+        assert_eq!(cells[0], (Coord(0, 0), &'a'));
+        assert_eq!(cells[4], (Coord(1, 1), &'e'));
+        assert!(g.enumerate().all(|(c, e)| g[c] == *e));
+    }
+}
@@ -0,0 +1,323 @@
+use super::{Grid, Neighbourhood};
+use crate::coordinates::Coord;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+// Shortest distances from a start cell, and the steps taken to get there
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paths {
+    start: Coord,
+    dist: Grid<Option<usize>>,
+    prev: Grid<Option<Coord>>,
+}
+
+impl Paths {
+    fn new<T>(grid: &Grid<T>, start: Coord) -> Option<Paths> {
+        let start = grid.resolve(start)?;
+        let mut dist = grid.map(|_| None);
+        dist[start] = Some(0);
+        Some(Paths {
+            start,
+            dist,
+            prev: grid.map(|_| None),
+        })
+    }
+
+    pub fn start(&self) -> Coord {
+        self.start
+    }
+
+    // None for cells that weren't reached
+    pub fn distance(&self, c: Coord) -> Option<usize> {
+        self.dist.get(c).copied().flatten()
+    }
+
+    pub fn distances(&self) -> &Grid<Option<usize>> {
+        &self.dist
+    }
+
+    // Every cell from the start to `end`, inclusive
+    pub fn path_to(&self, end: Coord) -> Option<Vec<Coord>> {
+        self.distance(end)?;
+        let mut path = vec![self.dist.resolve(end)?];
+        while let Some(prev) = self.prev[*path.last().unwrap()] {
+            path.push(prev);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    // Records a route to `c` through `from` if it beats the best so far
+    fn relax(&mut self, from: Coord, c: Coord, dist: usize) -> bool {
+        if self.dist[c].is_some_and(|best| best <= dist) {
+            return false;
+        }
+        self.dist[c] = Some(dist);
+        self.prev[c] = Some(from);
+        true
+    }
+}
+
+impl<T> Grid<T> {
+    // Unweighted distances from `start` to every reachable cell. The start
+    // cell itself doesn't need to be passable. None if `start` is outside
+    // the grid.
+    pub fn bfs<F>(
+        &self,
+        start: Coord,
+        neighbourhood: Neighbourhood,
+        mut passable: F,
+    ) -> Option<Paths>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut paths = Paths::new(self, start)?;
+        let mut queue = VecDeque::new();
+        queue.push_back(paths.start);
+
+        while let Some(c) = queue.pop_front() {
+            let dist = paths.dist[c].unwrap() + 1;
+            for n in self.neighbours(c, neighbourhood) {
+                if paths.dist[n].is_none() && passable(&self[n]) {
+                    paths.relax(c, n, dist);
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        Some(paths)
+    }
+
+    // Weighted distances from `start`, where `cost` gives the price of
+    // entering a cell, or None if it can't be entered. None if `start` is
+    // outside the grid.
+    pub fn dijkstra<F>(&self, start: Coord, neighbourhood: Neighbourhood, cost: F) -> Option<Paths>
+    where
+        F: FnMut(&T) -> Option<usize>,
+    {
+        self.best_first(start, None, neighbourhood, cost, |_| 0)
+    }
+
+    // The cheapest path from `start` to `goal` and its cost, with costs as
+    // for `dijkstra`. `heuristic` must never overestimate the remaining cost.
+    // None if `goal` is off the grid or can't be reached. None if `start` is
+    // outside the grid.
+    pub fn astar<F, H>(
+        &self,
+        start: Coord,
+        goal: Coord,
+        neighbourhood: Neighbourhood,
+        cost: F,
+        heuristic: H,
+    ) -> Option<(usize, Vec<Coord>)>
+    where
+        F: FnMut(&T) -> Option<usize>,
+        H: FnMut(Coord) -> usize,
+    {
+        let goal = self.resolve(goal)?;
+        let paths = self.best_first(start, Some(goal), neighbourhood, cost, heuristic)?;
+        Some((paths.distance(goal)?, paths.path_to(goal)?))
+    }
+
+    // Dijkstra's algorithm ordered by distance plus `heuristic`, stopping
+    // early once `goal` is settled
+    fn best_first<F, H>(
+        &self,
+        start: Coord,
+        goal: Option<Coord>,
+        neighbourhood: Neighbourhood,
+        mut cost: F,
+        mut heuristic: H,
+    ) -> Option<Paths>
+    where
+        F: FnMut(&T) -> Option<usize>,
+        H: FnMut(Coord) -> usize,
+    {
+        let mut paths = Paths::new(self, start)?;
+        let mut heap = BinaryHeap::new();
+        heap.push(Reverse((heuristic(paths.start), 0, paths.start)));
+
+        while let Some(Reverse((_, dist, c))) = heap.pop() {
+            // Skip entries superseded by a shorter route
+            if paths.dist[c].is_some_and(|best| best < dist) {
+                continue;
+            }
+            if Some(c) == goal {
+                break;
+            }
+
+            for n in self.neighbours(c, neighbourhood) {
+                let step = match cost(&self[n]) {
+                    None => continue,
+                    Some(step) => step,
+                };
+                if paths.relax(c, n, dist + step) {
+                    heap.push(Reverse((dist + step + heuristic(n), dist + step, n)));
+                }
+            }
+        }
+
+        Some(paths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Topology;
+    use crate::rng::Rng;
+
+    fn maze() -> Grid<char> {
+        ".....\n.###.\n...#.\n##.#.\n.....\n".parse().unwrap()
+    }
+
+    fn open(ch: &char) -> bool {
+        *ch != '#'
+    }
+
+    fn unit_cost(ch: &char) -> Option<usize> {
+        if open(ch) {
+            Some(1)
+        } else {
+            None
+        }
+    }
+
+    fn digit_cost(ch: &char) -> Option<usize> {
+        ch.to_digit(10).map(|d| d as usize)
+    }
+
+    fn manhattan(goal: Coord) -> impl FnMut(Coord) -> usize {
+        move |c| (goal - c).manhattan_len() as usize
+    }
+
+    #[test]
+    fn bfs_distances() {
+        let g = maze();
+        let paths = g.bfs(Coord(0, 0), Neighbourhood::VonNeumann, open).unwrap();
+        assert_eq!(paths.distance(Coord(0, 4)), Some(8));
+        assert_eq!(paths.distance(Coord(4, 4)), Some(8));
+        assert_eq!(paths.distance(Coord(2, 2)), Some(4));
+        assert_eq!(paths.distance(Coord(1, 1)), None);
+        assert_eq!(paths.distance(Coord(5, 0)), None);
+        assert_eq!(paths.distances().count_eq(&None), 7);
+
+        let path = paths.path_to(Coord(2, 4)).unwrap();
+        assert_eq!(
+            path,
+            [
+                Coord(0, 0),
+                Coord(0, 1),
+                Coord(0, 2),
+                Coord(1, 2),
+                Coord(2, 2),
+                Coord(2, 3),
+                Coord(2, 4),
+            ]
+        );
+        assert_eq!(paths.path_to(Coord(0, 0)), Some(vec![Coord(0, 0)]));
+        assert_eq!(paths.path_to(Coord(1, 1)), None);
+
+        // Diagonal steps can squeeze between walls
+        let paths = g.bfs(Coord(0, 0), Neighbourhood::Moore, open).unwrap();
+        assert_eq!(paths.distance(Coord(0, 4)), Some(5));
+    }
+
+    #[test]
+    fn off_grid_start() {
+        let g = maze();
+        let (start, nbhd) = (Coord(-1, 0), Neighbourhood::VonNeumann);
+        assert_eq!(g.bfs(start, nbhd, open), None);
+        assert_eq!(g.dijkstra(start, nbhd, unit_cost), None);
+        assert_eq!(g.astar(start, Coord(0, 0), nbhd, unit_cost, |_| 0), None);
+    }
+
+    #[test]
+    fn bfs_wrapping() {
+        let g: Grid<char> = ".....\n".parse().unwrap();
+        let g = g.with_topology(Topology::WrapHorizontal);
+        let paths = g.bfs(Coord(0, 0), Neighbourhood::VonNeumann, open).unwrap();
+        assert_eq!(paths.distance(Coord(4, 0)), Some(1));
+        assert_eq!(paths.distance(Coord(-1, 0)), Some(1));
+        assert_eq!(paths.path_to(Coord(-2, 0)).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn weighted_paths() {
+        let g: Grid<char> = "131\n191\n111\n".parse().unwrap();
+        let paths = g
+            .dijkstra(Coord(0, 0), Neighbourhood::VonNeumann, digit_cost)
+            .unwrap();
+        assert_eq!(paths.distance(Coord(2, 2)), Some(4));
+        assert_eq!(paths.distance(Coord(1, 1)), Some(10));
+        assert_eq!(
+            paths.path_to(Coord(2, 2)).unwrap(),
+            [
+                Coord(0, 0),
+                Coord(0, 1),
+                Coord(0, 2),
+                Coord(1, 2),
+                Coord(2, 2)
+            ]
+        );
+
+        let (cost, path) = g
+            .astar(
+                Coord(0, 0),
+                Coord(2, 2),
+                Neighbourhood::Moore,
+                digit_cost,
+                |_| 0,
+            )
+            .unwrap();
+        assert_eq!(cost, 3);
+        assert_eq!(path, [Coord(0, 0), Coord(0, 1), Coord(1, 2), Coord(2, 2)]);
+
+        let walled: Grid<char> = "1#1\n1#1\n".parse().unwrap();
+        let goal = Coord(2, 0);
+        let nbhd = Neighbourhood::VonNeumann;
+        assert_eq!(
+            walled.astar(Coord(0, 0), goal, nbhd, digit_cost, manhattan(goal)),
+            None
+        );
+        assert_eq!(
+            walled.astar(Coord(0, 0), Coord(3, 0), nbhd, digit_cost, |_| 0),
+            None
+        );
+        assert_eq!(
+            walled.astar(Coord(-1, 0), Coord(0, 1), nbhd, digit_cost, |_| 0),
+            None
+        );
+    }
+
+    #[test]
+    fn astar_matches_bfs() {
+        let mut rng = Rng::new(50);
+        for _ in 0..20 {
+            let g = Grid::from_fn(12, 9, |_| if rng.range(0..10) < 3 { '#' } else { '.' });
+            let start = Coord(0, 0);
+            let bfs = g.bfs(start, Neighbourhood::VonNeumann, open).unwrap();
+            let dijkstra = g
+                .dijkstra(start, Neighbourhood::VonNeumann, unit_cost)
+                .unwrap();
+            assert_eq!(bfs.distances(), dijkstra.distances());
+
+            for goal in g.coords() {
+                let found = g.astar(
+                    start,
+                    goal,
+                    Neighbourhood::VonNeumann,
+                    unit_cost,
+                    manhattan(goal),
+                );
+                assert_eq!(found.as_ref().map(|f| f.0), bfs.distance(goal));
+                if let Some((dist, path)) = found {
+                    assert_eq!(path.len(), dist + 1);
+                    assert_eq!((path[0], path[dist]), (start, goal));
+                    assert!(path[1..].iter().all(|&c| open(&g[c])));
+                    assert!(path.windows(2).all(|w| (w[1] - w[0]).manhattan_len() == 1));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod aoc_input;
+pub mod coordinates;
+pub mod fuzz;
+pub mod grid;
+pub mod rng;
+pub mod symmetry;
+pub mod watch;
@@ -0,0 +1,373 @@
+use crate::coordinates::{Coord, CoordN, Delta, DeltaN, Turn};
+use crate::grid::{Axis, Rotation};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+// An element of the dihedral group of the square: a mirror across the
+// horizontal axis (if `flipped`) followed by `cw_count` clockwise quarter
+// turns. Coordinates are screen-style, with y growing downwards.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct D4 {
+    cw_count: u8,
+    flipped: bool,
+}
+
+impl D4 {
+    pub const IDENTITY: D4 = D4 {
+        cw_count: 0,
+        flipped: false,
+    };
+
+    // Mirrors top and bottom, like `Grid::flip_inplace(Axis::Horizontal)`
+    pub const FLIP: D4 = D4 {
+        cw_count: 0,
+        flipped: true,
+    };
+
+    // The four rotations first, then the four flipped variants
+    pub const ALL: [D4; 8] = [
+        D4::new(0, false),
+        D4::new(1, false),
+        D4::new(2, false),
+        D4::new(3, false),
+        D4::new(0, true),
+        D4::new(1, true),
+        D4::new(2, true),
+        D4::new(3, true),
+    ];
+
+    const fn new(cw_count: u8, flipped: bool) -> D4 {
+        D4 { cw_count, flipped }
+    }
+
+    pub fn rotate_cw(quarter_turns: isize) -> D4 {
+        D4::new(quarter_turns.rem_euclid(4) as u8, false)
+    }
+
+    pub fn cw_count(self) -> usize {
+        self.cw_count as usize
+    }
+
+    pub fn is_flipped(self) -> bool {
+        self.flipped
+    }
+
+    // The transform that applies `self` and then `next`
+    pub fn then(self, next: D4) -> D4 {
+        // A flip reverses the direction of any rotation applied before it
+        let before = if next.flipped {
+            4 - self.cw_count
+        } else {
+            self.cw_count
+        };
+        D4::new((before + next.cw_count) % 4, self.flipped != next.flipped)
+    }
+
+    pub fn inverse(self) -> D4 {
+        if self.flipped {
+            self
+        } else {
+            D4::new((4 - self.cw_count) % 4, false)
+        }
+    }
+
+    pub fn apply_delta(self, d: Delta) -> Delta {
+        let d = if self.flipped { Delta(d.0, -d.1) } else { d };
+        d.turn(Turn::try_from(self.cw_count).unwrap())
+    }
+
+    // Maps a coordinate of a `width` x `height` grid to its position in the
+    // transformed grid, whose dimensions are given by `dims`
+    pub fn apply_coord(self, c: Coord, width: usize, height: usize) -> Coord {
+        let far = Delta(width as isize - 1, height as isize - 1);
+        let corners = [self.apply_delta(Delta(0, 0)), self.apply_delta(far)];
+        let offset = corners[0].component_min(corners[1]);
+        Coord::origin() + (self.apply_delta(c - Coord::origin()) - offset)
+    }
+
+    pub fn dims(self, width: usize, height: usize) -> (usize, usize) {
+        if self.cw_count.is_multiple_of(2) {
+            (width, height)
+        } else {
+            (height, width)
+        }
+    }
+}
+
+impl Default for D4 {
+    fn default() -> Self {
+        D4::IDENTITY
+    }
+}
+
+impl From<Turn> for D4 {
+    fn from(t: Turn) -> Self {
+        D4::new(u8::from(t), false)
+    }
+}
+
+impl From<Rotation> for D4 {
+    fn from(r: Rotation) -> Self {
+        D4::new(r.to_cw_count() as u8, false)
+    }
+}
+
+impl From<Axis> for D4 {
+    fn from(axis: Axis) -> Self {
+        match axis {
+            Axis::Horizontal => D4::FLIP,
+            Axis::Vertical => D4::new(2, true),
+        }
+    }
+}
+
+// One of the 24 proper rotations of 3D space, stored as a signed
+// permutation matrix with determinant 1
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Rotation3 {
+    m: [[isize; 3]; 3],
+}
+
+// A transform mapping points onto a reference cloud, and how many matched
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Alignment {
+    pub rotation: Rotation3,
+    pub translation: DeltaN<3>,
+    pub overlap: usize,
+}
+
+impl Rotation3 {
+    pub const IDENTITY: Rotation3 = Rotation3 {
+        m: [[1, 0, 0], [0, 1, 0], [0, 0, 1]],
+    };
+
+    pub fn all() -> [Rotation3; 24] {
+        const PERMUTATIONS: [[usize; 3]; 6] = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+
+        let mut res = [Rotation3::IDENTITY; 24];
+        let mut count = 0;
+        for perm in &PERMUTATIONS {
+            for signs in 0..8 {
+                let mut m = [[0; 3]; 3];
+                for (row, &col) in perm.iter().enumerate() {
+                    m[row][col] = if signs & (1 << row) == 0 { 1 } else { -1 };
+                }
+                let r = Rotation3 { m };
+                if r.determinant() == 1 {
+                    res[count] = r;
+                    count += 1;
+                }
+            }
+        }
+        res
+    }
+
+    fn determinant(&self) -> isize {
+        let m = &self.m;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    pub fn apply_delta(&self, d: DeltaN<3>) -> DeltaN<3> {
+        let mut res = [0; 3];
+        for (r, row) in res.iter_mut().zip(self.m.iter()) {
+            *r = row.iter().zip(d.0.iter()).map(|(a, b)| a * b).sum();
+        }
+        DeltaN(res)
+    }
+
+    // Rotates around the origin
+    pub fn apply_coord(&self, c: CoordN<3>) -> CoordN<3> {
+        CoordN::origin() + self.apply_delta(c - CoordN::origin())
+    }
+
+    // The rotation that applies `self` and then `next`
+    pub fn then(&self, next: &Rotation3) -> Rotation3 {
+        let mut m = [[0; 3]; 3];
+        for (i, row) in m.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = (0..3).map(|k| next.m[i][k] * self.m[k][j]).sum();
+            }
+        }
+        Rotation3 { m }
+    }
+
+    // Rotation matrices are orthogonal, so the inverse is the transpose
+    pub fn inverse(&self) -> Rotation3 {
+        let mut m = [[0; 3]; 3];
+        for (i, row) in m.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = self.m[j][i];
+            }
+        }
+        Rotation3 { m }
+    }
+
+    // Finds the rotation and translation mapping the most `points` onto
+    // `reference`, if at least `min_overlap` of them match
+    pub fn align(
+        reference: &[CoordN<3>],
+        points: &[CoordN<3>],
+        min_overlap: usize,
+    ) -> Option<Alignment> {
+        let mut best: Option<Alignment> = None;
+        for rotation in Rotation3::all().iter() {
+            let mut votes = HashMap::<DeltaN<3>, usize>::new();
+            for &p in points {
+                let rotated = rotation.apply_coord(p);
+                for &r in reference {
+                    *votes.entry(r - rotated).or_default() += 1;
+                }
+            }
+
+            // Ties go to the smallest translation, as HashMap order varies
+            let best_vote = votes.into_iter().max_by_key(|&(d, n)| (n, Reverse(d)));
+            let (translation, overlap) = match best_vote {
+                None => continue,
+                Some(v) => v,
+            };
+            if overlap >= min_overlap && best.is_none_or(|b| overlap > b.overlap) {
+                best = Some(Alignment {
+                    rotation: *rotation,
+                    translation,
+                    overlap,
+                });
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+    use crate::rng::Rng;
+    use std::collections::HashSet;
+
+    const BASIS: [Delta; 2] = [Delta(1, 0), Delta(0, 1)];
+
+    #[test]
+    fn all_distinct() {
+        let images: HashSet<_> = D4::ALL
+            .iter()
+            .map(|t| (t.apply_delta(BASIS[0]), t.apply_delta(BASIS[1])))
+            .collect();
+        assert_eq!(images.len(), 8);
+    }
+
+    #[test]
+    fn composition_and_inverse() {
+        for &a in &D4::ALL {
+            assert_eq!(a.then(a.inverse()), D4::IDENTITY);
+            assert_eq!(a.inverse().then(a), D4::IDENTITY);
+            for &b in &D4::ALL {
+                let ab = a.then(b);
+                for &d in &BASIS {
+                    assert_eq!(ab.apply_delta(d), b.apply_delta(a.apply_delta(d)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn named_transforms() {
+        assert_eq!(D4::rotate_cw(1).apply_delta(Delta(0, -1)), Delta(1, 0));
+        assert_eq!(D4::rotate_cw(-1), D4::rotate_cw(3));
+        assert_eq!(D4::FLIP.apply_delta(Delta(2, 3)), Delta(2, -3));
+        assert_eq!(
+            D4::from(Axis::Vertical).apply_delta(Delta(2, 3)),
+            Delta(-2, 3)
+        );
+        assert_eq!(D4::from(Turn::Left), D4::rotate_cw(3));
+        assert_eq!(D4::from(Rotation::Cw180), D4::rotate_cw(2));
+    }
+
+    #[test]
+    fn matches_grid() {
+        let grid: Grid<char> = "abc\ndef\n".parse().unwrap();
+        for &t in &D4::ALL {
+            let mut transformed = grid.clone();
+            transformed.transform_inplace(t);
+            let (w, h) = t.dims(grid.width(), grid.height());
+            assert_eq!((transformed.width(), transformed.height()), (w, h));
+            for y in 0..grid.height() as isize {
+                for x in 0..grid.width() as isize {
+                    let c = Coord(x, y);
+                    let mapped = t.apply_coord(c, grid.width(), grid.height());
+                    assert_eq!(transformed.get(mapped), grid.get(c));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rotation3_group() {
+        let all = Rotation3::all();
+        let distinct: HashSet<_> = all.iter().collect();
+        assert_eq!(distinct.len(), 24);
+        assert!(distinct.contains(&Rotation3::IDENTITY));
+
+        let d = DeltaN([1, 2, 3]);
+        for a in all.iter() {
+            assert_eq!(a.then(&a.inverse()), Rotation3::IDENTITY);
+            assert_eq!(a.apply_delta(d).squared_len(), d.squared_len());
+            for b in all.iter() {
+                let ab = a.then(b);
+                assert!(distinct.contains(&ab));
+                assert_eq!(ab.apply_delta(d), b.apply_delta(a.apply_delta(d)));
+            }
+        }
+
+        // Every orientation of an asymmetric vector is distinct
+        let images: HashSet<_> = all.iter().map(|r| r.apply_delta(d)).collect();
+        assert_eq!(images.len(), 24);
+    }
+
+    #[test]
+    fn rotation3_align_ties() {
+        // One point matches any of the reference points equally well
+        let reference = [CoordN([5, 0, 0]), CoordN([1, 0, 0]), CoordN([3, 0, 0])];
+        for _ in 0..10 {
+            let alignment = Rotation3::align(&reference, &[CoordN::origin()], 1).unwrap();
+            assert_eq!(alignment.rotation, Rotation3::IDENTITY);
+            assert_eq!(alignment.translation, DeltaN([1, 0, 0]));
+        }
+    }
+
+    #[test]
+    fn rotation3_align() {
+        let mut rng = Rng::new(38);
+        let reference: Vec<CoordN<3>> = (0..25)
+            .map(|_| CoordN([0; 3].map(|_: isize| rng.range(-1000..1000))))
+            .collect();
+
+        for _ in 0..10 {
+            let rotation = *rng.choose(&Rotation3::all());
+            let offset = DeltaN([0; 3].map(|_: isize| rng.range(-500..500)));
+            // Express the first 15 reference points relative to an unknown
+            // frame, plus some points the reference cloud doesn't contain
+            let mut points: Vec<_> = reference[..15]
+                .iter()
+                .map(|&p| rotation.inverse().apply_coord(p - offset))
+                .collect();
+            points.extend((0..10).map(|_| CoordN([0; 3].map(|_: isize| rng.range(2000..3000)))));
+            rng.shuffle(&mut points);
+
+            let alignment = Rotation3::align(&reference, &points, 12).unwrap();
+            assert_eq!(alignment.rotation, rotation);
+            assert_eq!(alignment.translation, offset);
+            assert_eq!(alignment.overlap, 15);
+            assert!(Rotation3::align(&reference, &points, 16).is_none());
+        }
+    }
+}
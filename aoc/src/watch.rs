@@ -0,0 +1,230 @@
+use crate::aoc_input::cache_path;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+type Snapshot = HashMap<PathBuf, Option<SystemTime>>;
+
+pub fn workspace_root() -> Option<PathBuf> {
+    let cwd = std::env::current_dir().ok()?;
+    cwd.ancestors()
+        .find(|dir| {
+            fs::read_to_string(dir.join("Cargo.toml"))
+                .map(|manifest| manifest.contains("[workspace]"))
+                .unwrap_or(false)
+        })
+        .map(Path::to_path_buf)
+}
+
+pub fn latest_year(root: &Path) -> Option<u16> {
+    fs::read_dir(root)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            name.strip_prefix("aoc")?.parse().ok()
+        })
+        .max()
+}
+
+fn package(year: u16) -> String {
+    format!("aoc{}", year)
+}
+
+// The day's source, any example files next to it (e.g. day20_test_tiles.txt)
+// and the cached input used by release builds of the solver.
+fn watched_files(root: &Path, year: u16, day: u8) -> Vec<PathBuf> {
+    let bin_dir = root.join(package(year)).join("src").join("bin");
+    let example_prefix = format!("day{}_", day);
+
+    let mut files = vec![bin_dir.join(format!("day{}.rs", day))];
+    if let Ok(entries) = fs::read_dir(&bin_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with(&example_prefix) {
+                files.push(entry.path());
+            }
+        }
+    }
+    files.push(input_cache(root, year, day));
+    files.sort();
+    files
+}
+
+fn input_cache(root: &Path, year: u16, day: u8) -> PathBuf {
+    let release_dir = root.join("target").join("release");
+    cache_path(&release_dir, year, day)
+}
+
+fn mtime(file: &Path) -> Option<SystemTime> {
+    fs::metadata(file).and_then(|m| m.modified()).ok()
+}
+
+fn snapshot(files: &[PathBuf]) -> Snapshot {
+    files.iter().map(|f| (f.clone(), mtime(f))).collect()
+}
+
+fn cargo(root: &Path, args: &[&str]) -> (bool, String, String) {
+    match Command::new("cargo").args(args).current_dir(root).output() {
+        Err(e) => (false, String::new(), e.to_string()),
+        Ok(output) => (
+            output.status.success(),
+            String::from_utf8_lossy(&output.stdout).into_owned(),
+            String::from_utf8_lossy(&output.stderr).into_owned(),
+        ),
+    }
+}
+
+fn is_dbg_line(line: &str) -> bool {
+    line.starts_with('[') && line.contains(".rs:") && line.contains("] ")
+}
+
+// Answers are whatever the solver prints: println! lines on stdout (minus
+// input cache chatter), and dbg! output on stderr including the
+// continuation lines of pretty-printed values.
+pub fn extract_answers(stdout: &str, stderr: &str) -> Vec<String> {
+    let mut answers: Vec<String> = stdout
+        .lines()
+        .filter(|line| !line.starts_with("Cache hit") && !line.starts_with("Cache miss"))
+        .map(str::to_owned)
+        .collect();
+
+    let mut in_dbg = false;
+    for line in stderr.lines() {
+        if is_dbg_line(line) {
+            in_dbg = true;
+        } else if !(in_dbg && line.starts_with(|c: char| c.is_whitespace() || "}])".contains(c))) {
+            in_dbg = false;
+            continue;
+        }
+        answers.push(line.to_owned());
+    }
+    answers
+}
+
+pub fn diff_lines(old: &[String], new: &[String]) -> Vec<String> {
+    let mut diff = Vec::new();
+    for i in 0..old.len().max(new.len()) {
+        match (old.get(i), new.get(i)) {
+            (Some(a), Some(b)) if a == b => diff.push(format!("  {}", a)),
+            (a, b) => {
+                diff.extend(a.map(|a| format!("- {}", a)));
+                diff.extend(b.map(|b| format!("+ {}", b)));
+            }
+        }
+    }
+    diff
+}
+
+fn run_once(
+    root: &Path,
+    year: u16,
+    day: u8,
+    previous: &Option<Vec<String>>,
+) -> Option<Vec<String>> {
+    let package = package(year);
+    let bin = format!("day{}", day);
+
+    println!("==> Testing {} {}", package, bin);
+    let (ok, stdout, stderr) = cargo(root, &["test", "-q", "-p", &package, "--bin", &bin]);
+    if !ok {
+        print!("{}{}", stdout, stderr);
+        println!("==> Tests failed");
+        return None;
+    }
+    println!("==> Tests passed");
+
+    println!("==> Running {} {}", package, bin);
+    let args = ["run", "-q", "--release", "-p", &package, "--bin", &bin];
+    let (ok, stdout, stderr) = cargo(root, &args);
+    if !ok {
+        print!("{}{}", stdout, stderr);
+        println!("==> Solver failed");
+        return None;
+    }
+
+    let answers = extract_answers(&stdout, &stderr);
+    let lines = match previous {
+        None => answers.iter().map(|a| format!("  {}", a)).collect(),
+        Some(previous) => diff_lines(previous, &answers),
+    };
+    for line in lines {
+        println!("{}", line);
+    }
+    Some(answers)
+}
+
+pub fn watch(root: &Path, year: u16, day: u8) -> ! {
+    let mut previous = None;
+    let mut last = Snapshot::new();
+
+    loop {
+        let current = snapshot(&watched_files(root, year, day));
+        if current != last {
+            last = current;
+            if let Some(answers) = run_once(root, year, day, &previous) {
+                previous = Some(answers);
+            }
+            // The solver writes the cache on a miss, which isn't a change
+            // worth rerunning for. Other files keep their pre-run times so
+            // edits made during the run are still picked up.
+            let cache = input_cache(root, year, day);
+            let cache_mtime = mtime(&cache);
+            last.insert(cache, cache_mtime);
+            println!("==> Watching for changes...");
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|&l| l.to_owned()).collect()
+    }
+
+    #[test]
+    fn answers_from_output() {
+        let stdout = "Cache miss for day 1\n42\nCache hit for day 1\nDone\n";
+        let stderr = "\
+[aoc2020/src/bin/day1.rs:10:5] x = 5
+warning: something unrelated
+    with an indented note
+[aoc2020/src/bin/day1.rs:11:5] v = [
+    1,
+    2,
+]
+error: trailing
+";
+        assert_eq!(
+            extract_answers(stdout, stderr),
+            [
+                "42",
+                "Done",
+                "[aoc2020/src/bin/day1.rs:10:5] x = 5",
+                "[aoc2020/src/bin/day1.rs:11:5] v = [",
+                "    1,",
+                "    2,",
+                "]",
+            ]
+        );
+        assert!(extract_answers("Cache hit\n", "").is_empty());
+    }
+
+    #[test]
+    fn diffs() {
+        let old = strings(&["a", "b", "c"]);
+        let new = strings(&["a", "x"]);
+        assert_eq!(diff_lines(&old, &new), ["  a", "- b", "+ x", "- c"]);
+        assert_eq!(diff_lines(&new, &old), ["  a", "- x", "+ b", "+ c"]);
+        assert_eq!(diff_lines(&[], &new), ["+ a", "+ x"]);
+        assert_eq!(diff_lines(&old, &old), ["  a", "  b", "  c"]);
+    }
+}
@@ -0,0 +1,5 @@
+const YEAR: u16 = 2020;
+
+pub fn get_input(day: u8) -> String {
+    aoc::aoc_input::get_input(YEAR, day)
+}
@@ -1,9 +1,16 @@
 use aoc2020::aoc_input::get_input;
 use std::collections::HashSet;
 
+fn parse_input(input: &str) -> Result<HashSet<u64>, &'static str> {
+    input
+        .lines()
+        .map(|n| n.parse::<u64>().or(Err("Invalid entry")))
+        .collect()
+}
+
 fn main() {
     let input = get_input(1);
-    let entries: HashSet<_> = input.lines().map(|n| n.parse::<u64>().unwrap()).collect();
+    let entries = parse_input(&input).unwrap();
 
     for entry in entries.iter().copied() {
         let other = 2020 - entry;
@@ -27,3 +34,14 @@ fn main() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::fuzz::check_parser;
+
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(&["1721\n979\n366\n299\n675\n1456\n"], parse_input);
+    }
+}
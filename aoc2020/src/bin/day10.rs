@@ -0,0 +1,117 @@
+use aoc2020::aoc_input::get_input;
+
+fn jolts_ascending(input: &str) -> Result<Vec<usize>, &'static str> {
+    let mut jolts = vec![0usize];
+    for line in input.lines() {
+        jolts.push(line.parse().or(Err("Invalid joltage"))?);
+    }
+    jolts.sort_unstable();
+    let device = jolts
+        .last()
+        .unwrap()
+        .checked_add(3)
+        .ok_or("Joltage too large")?;
+    jolts.push(device);
+    Ok(jolts)
+}
+
+fn arrangements(jolts: &[usize]) -> usize {
+    let len = jolts.len();
+    if len < 2 {
+        panic!("Invalid length");
+    }
+    if len == 2 {
+        return 1;
+    }
+
+    let pivot = len / 2;
+    let delta = jolts[pivot + 1] - jolts[pivot - 1];
+    let left = &jolts[..=pivot];
+    let right = &jolts[pivot..];
+
+    let mut result = arrangements(left) * arrangements(right);
+    if delta <= 3 {
+        let mut without_pivot = Vec::with_capacity(len - 1);
+        without_pivot.extend_from_slice(&jolts[..pivot]);
+        without_pivot.extend_from_slice(&jolts[pivot + 1..]);
+        result += arrangements(&without_pivot);
+    };
+
+    result
+}
+
+fn main() {
+    let input = get_input(10);
+    let jolts = jolts_ascending(&input).unwrap();
+
+    let mut deltas_hist = [0usize; 4];
+    for w in jolts.windows(2) {
+        let delta = w[1] - w[0];
+        assert!(delta < deltas_hist.len());
+        deltas_hist[delta] += 1;
+    }
+
+    dbg!(deltas_hist[1] * deltas_hist[3]);
+    dbg!(arrangements(&jolts));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::fuzz::check_parser;
+    use aoc2020::rng::Rng;
+
+    fn brute_arrangements(jolts: &[usize]) -> usize {
+        let (first, last) = (jolts[0], jolts[jolts.len() - 1]);
+        let middle = &jolts[1..jolts.len() - 1];
+
+        (0..1usize << middle.len())
+            .filter(|subset| {
+                let mut chain = vec![first];
+                for (i, j) in middle.iter().enumerate() {
+                    if subset & (1 << i) != 0 {
+                        chain.push(*j);
+                    }
+                }
+                chain.push(last);
+                chain.windows(2).all(|w| w[1] - w[0] <= 3)
+            })
+            .count()
+    }
+
+    fn random_adapters(rng: &mut Rng, count: usize) -> String {
+        let mut jolt = 0usize;
+        let mut adapters: Vec<_> = (0..count)
+            .map(|_| {
+                jolt += 1 + rng.below(3);
+                jolt.to_string()
+            })
+            .collect();
+        rng.shuffle(&mut adapters);
+        adapters.join("\n")
+    }
+
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(
+            &["16\n10\n15\n5\n1\n11\n7\n19\n6\n12\n4\n"],
+            jolts_ascending,
+        );
+    }
+
+    #[test]
+    fn arrangements_matches_brute_force() {
+        let mut rng = Rng::new(10);
+        for count in 1..=14 {
+            for _ in 0..10 {
+                let jolts = jolts_ascending(&random_adapters(&mut rng, count)).unwrap();
+                assert_eq!(
+                    arrangements(&jolts),
+                    brute_arrangements(&jolts),
+                    "{:?}",
+                    jolts
+                );
+            }
+        }
+    }
+}
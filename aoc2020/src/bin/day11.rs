@@ -40,20 +40,9 @@ impl Simulation {
     }
 
     fn decide1(&self, c: Coord) -> Option<Position> {
-        let dirs = [
-            Delta(-1, 0),
-            Delta(0, -1),
-            Delta(1, 0),
-            Delta(0, 1),
-            Delta(-1, -1),
-            Delta(1, -1),
-            Delta(-1, 1),
-            Delta(1, 1),
-        ];
-
-        let occupied = dirs
-            .iter()
-            .filter(|d| self.grid.get(c + **d) == Some(&Position::Occupied))
+        let occupied = c
+            .moore()
+            .filter(|n| self.grid.get(*n) == Some(&Position::Occupied))
             .count();
         let current = *self.grid.get(c).unwrap();
 
@@ -67,10 +56,8 @@ impl Simulation {
     }
 
     fn seat_in_direction(&self, c: Coord, dir: Delta) -> Option<Coord> {
-        for scale in 1isize.. {
-            let delta = scale * dir;
-            let coord = c + delta;
-            match self.grid.get(coord).to_owned() {
+        for coord in c.ray(dir) {
+            match self.grid.get(coord) {
                 None => return None,
                 Some(Position::Floor) => continue,
                 Some(_) => return Some(coord),
@@ -80,18 +67,7 @@ impl Simulation {
     }
 
     fn decide2(&self, c: Coord) -> Option<Position> {
-        let dirs = [
-            Delta(-1, 0),
-            Delta(0, -1),
-            Delta(1, 0),
-            Delta(0, 1),
-            Delta(-1, -1),
-            Delta(1, -1),
-            Delta(-1, 1),
-            Delta(1, 1),
-        ];
-
-        let seats: Vec<_> = dirs
+        let seats: Vec<_> = Delta::MOORE
             .iter()
             .filter_map(|d| self.seat_in_direction(c, *d))
             .collect();
@@ -115,17 +91,14 @@ impl Simulation {
         let mut next_grid = self.grid.clone();
         let mut changed = false;
 
-        for x in 0..next_grid.width() {
-            for y in 0..next_grid.height() {
-                let coord = Coord(x as isize, y as isize);
-                let res = match part {
-                    Part::One => self.decide1(coord),
-                    Part::Two => self.decide2(coord),
-                };
-                if let Some(p) = res {
-                    changed = true;
-                    *next_grid.get_mut(coord).unwrap() = p;
-                }
+        for coord in self.grid.coords() {
+            let res = match part {
+                Part::One => self.decide1(coord),
+                Part::Two => self.decide2(coord),
+            };
+            if let Some(p) = res {
+                changed = true;
+                next_grid[coord] = p;
             }
         }
 
@@ -146,9 +119,13 @@ impl Simulation {
     }
 }
 
+fn parse_input(input: &str) -> Result<Grid<Position>, &'static str> {
+    input.parse()
+}
+
 fn main() {
     let input = get_input(11);
-    let grid: Grid<Position> = input.parse().unwrap();
+    let grid = parse_input(&input).unwrap();
 
     let mut sim1 = Simulation::new(grid);
     let mut sim2 = sim1.clone();
@@ -159,3 +136,17 @@ fn main() {
     sim2.run2();
     dbg!(sim2.count_of(Position::Occupied));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::fuzz::check_parser;
+
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(
+            &["L.LL.LL.LL\nLLLLLLL.LL\nL.L.L..L..\nLLLL.LL.LL\nL.LL.LL.LL\n"],
+            parse_input,
+        );
+    }
+}
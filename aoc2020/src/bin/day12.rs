@@ -1,5 +1,6 @@
 use aoc2020::aoc_input::get_input;
-use aoc2020::coordinates::{manhattan_distance, Coord, Delta, Direction, Turn};
+use aoc2020::coordinates::{manhattan_distance, Compass, Coord, Delta, Direction, Turn};
+use std::convert::TryFrom;
 use std::str::FromStr;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -20,10 +21,10 @@ impl FromStr for Instruction {
         let num = isize::from_str(rest).or(Err("Invalid number"))?;
 
         match first {
-            'N' => Ok(Instruction::Move(Direction::Up, num)),
-            'S' => Ok(Instruction::Move(Direction::Down, num)),
-            'E' => Ok(Instruction::Move(Direction::Right, num)),
-            'W' => Ok(Instruction::Move(Direction::Left, num)),
+            'N' | 'S' | 'E' | 'W' => {
+                let dir = Direction::try_from(Compass::try_from(first)?)?;
+                Ok(Instruction::Move(dir, num))
+            }
             'L' => Ok(Instruction::Turn((num / 90) * Turn::Left)),
             'R' => Ok(Instruction::Turn((num / 90) * Turn::Right)),
             'F' => Ok(Instruction::Forward(num)),
@@ -105,9 +106,13 @@ impl Simulation {
     }
 }
 
+fn parse_input(input: &str) -> Result<Vec<Instruction>, &'static str> {
+    input.lines().map(|line| line.parse()).collect()
+}
+
 fn main() {
     let input = get_input(12);
-    let insns: Vec<Instruction> = input.lines().map(|line| line.parse().unwrap()).collect();
+    let insns = parse_input(&input).unwrap();
 
     let mut sim1 = Simulation::new();
     sim1.ship_run_all(&insns);
@@ -117,3 +122,14 @@ fn main() {
     sim2.waypoint_run_all(&insns);
     dbg!(sim2.distance_from_origin());
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::fuzz::check_parser;
+
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(&["F10\nN3\nF7\nR90\nF11\n"], parse_input);
+    }
+}
@@ -0,0 +1,128 @@
+use aoc2020::aoc_input::get_input;
+use num_bigint::{BigInt, Sign, ToBigInt};
+use num_integer::Integer;
+use num_traits::One;
+
+fn parse_input(input: &str) -> Result<(usize, Vec<Option<usize>>), &'static str> {
+    let mut lines = input.lines();
+    let min_depart = lines.next().ok_or("No departure line")?;
+    let min_depart: usize = min_depart.parse().or(Err("Invalid departure"))?;
+    let buses = lines.next().ok_or("No buses line")?.split(',');
+
+    let mut bus_ids = Vec::new();
+    for bus in buses {
+        let id = match bus {
+            "x" => None,
+            _ => Some(bus.parse().or(Err("Invalid bus id"))?),
+        };
+        if id == Some(0) {
+            return Err("Invalid bus id");
+        }
+        bus_ids.push(id);
+    }
+
+    if bus_ids.iter().all(Option::is_none) {
+        return Err("No buses");
+    }
+    Ok((min_depart, bus_ids))
+}
+
+fn part1(min_depart: usize, bus_ids: &[Option<usize>]) {
+    let (bus_id, bus_depart) = bus_ids
+        .iter()
+        .copied()
+        .flatten()
+        .map(|id| (id, Integer::next_multiple_of(&min_depart, &id)))
+        .min_by_key(|(_, depart)| *depart)
+        .unwrap();
+    dbg!(bus_id * (bus_depart - min_depart));
+}
+
+fn earliest_timestamp(bus_ids: &[Option<usize>]) -> BigInt {
+    let mut constraints = Vec::with_capacity(bus_ids.len());
+    for (i, id) in bus_ids.iter().enumerate() {
+        if let Some(id) = id {
+            constraints.push((i.to_bigint().unwrap(), id.to_bigint().unwrap()));
+        }
+    }
+    let constraints = constraints;
+    let (mut rem1, mut mod1) = constraints[0].clone();
+    for (rem2, mod2) in constraints[1..].iter() {
+        let egcd = BigInt::extended_gcd(&mod1, mod2);
+        assert!(egcd.gcd.is_one());
+
+        let new_mod = mod1.clone() * mod2;
+        rem1 = (rem2 * egcd.x * mod1 + rem1 * egcd.y * mod2) % new_mod.clone();
+        if rem1.sign() == Sign::Minus {
+            rem1 += new_mod.clone();
+        }
+        mod1 = new_mod;
+    }
+    (-rem1).mod_floor(&mod1)
+}
+
+fn part2(bus_ids: &[Option<usize>]) {
+    dbg!(earliest_timestamp(bus_ids).to_string());
+}
+
+fn main() {
+    let input = get_input(13);
+    let (min_depart, bus_ids) = parse_input(&input).unwrap();
+    part1(min_depart, &bus_ids);
+    part2(&bus_ids);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::fuzz::check_parser;
+    use aoc2020::rng::Rng;
+
+    const PRIMES: &[usize] = &[2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+    fn brute_earliest_timestamp(bus_ids: &[Option<usize>]) -> usize {
+        (0..)
+            .find(|t| {
+                bus_ids
+                    .iter()
+                    .enumerate()
+                    .all(|(i, id)| id.is_none_or(|id| (t + i) % id == 0))
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(&["939\n7,13,x,x,59,x,31,19\n"], parse_input);
+    }
+
+    #[test]
+    fn test_earliest_timestamp() {
+        let (_, bus_ids) = parse_input("939\n7,13,x,x,59,x,31,19\n").unwrap();
+        assert_eq!(earliest_timestamp(&bus_ids), 1068781.into());
+    }
+
+    #[test]
+    fn earliest_timestamp_matches_brute_force() {
+        let mut rng = Rng::new(13);
+        for _ in 0..200 {
+            let mut primes = PRIMES.to_vec();
+            rng.shuffle(&mut primes);
+            let mut bus_ids: Vec<_> = primes[..1 + rng.below(4)]
+                .iter()
+                .copied()
+                .map(Some)
+                .collect();
+            bus_ids.extend((0..rng.below(6)).map(|_| None));
+            rng.shuffle(&mut bus_ids);
+
+            let expected = brute_earliest_timestamp(&bus_ids);
+            assert_eq!(
+                earliest_timestamp(&bus_ids),
+                expected.into(),
+                "{:?}",
+                bus_ids
+            );
+        }
+    }
+}
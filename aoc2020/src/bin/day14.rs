@@ -44,6 +44,10 @@ impl FromStr for Mask {
     type Err = &'static str;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 36 {
+            return Err("Invalid mask string length");
+        }
+
         let mut mask = Mask::default();
         for (i, c) in s.chars().rev().enumerate() {
             let bit = 1u64 << i;
@@ -151,9 +155,13 @@ impl Machine {
     }
 }
 
+fn parse_input(input: &str) -> Result<Vec<Instruction>, &'static str> {
+    input.lines().map(|s| s.parse()).collect()
+}
+
 fn main() {
     let input = get_input(14);
-    let insns: Vec<Instruction> = input.lines().map(|s| s.parse().unwrap()).collect();
+    let insns = parse_input(&input).unwrap();
 
     let mut v1 = Machine::new(Version::One);
     v1.run_multi(&insns);
@@ -163,3 +171,101 @@ fn main() {
     v2.run_multi(&insns);
     dbg!(v2.mem_sum());
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::fuzz::check_parser;
+    use aoc2020::rng::Rng;
+
+    const BITS: usize = 36;
+
+    fn bit_at(value: u64, i: usize) -> char {
+        if value & (1 << (BITS - 1 - i)) != 0 {
+            '1'
+        } else {
+            '0'
+        }
+    }
+
+    fn from_binary(s: &str) -> u64 {
+        u64::from_str_radix(s, 2).unwrap()
+    }
+
+    // Both references apply the mask character by character to the binary
+    // string of the value, most significant bit first.
+    fn naive_mask(mask: &str, data: u64) -> u64 {
+        let masked: String = mask
+            .chars()
+            .enumerate()
+            .map(|(i, c)| if c == 'X' { bit_at(data, i) } else { c })
+            .collect();
+        from_binary(&masked)
+    }
+
+    fn naive_addresses(mask: &str, addr: u64) -> Vec<u64> {
+        let mut prefixes = vec![String::new()];
+        for (i, c) in mask.chars().enumerate() {
+            let options = match c {
+                '0' => vec![bit_at(addr, i)],
+                '1' => vec!['1'],
+                _ => vec!['0', '1'],
+            };
+            prefixes = prefixes
+                .iter()
+                .flat_map(|p| options.iter().map(move |o| format!("{}{}", p, o)))
+                .collect();
+        }
+        prefixes.iter().map(|s| from_binary(s)).collect()
+    }
+
+    fn random_mask(rng: &mut Rng, floating: usize) -> String {
+        let mut mask: Vec<char> = (0..BITS)
+            .map(|_| if rng.chance(1, 2) { '1' } else { '0' })
+            .collect();
+        for _ in 0..floating {
+            mask[rng.below(BITS)] = 'X';
+        }
+        mask.into_iter().collect()
+    }
+
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(
+            &["mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X\n\
+             mem[8] = 11\n\
+             mem[7] = 101\n\
+             mem[8] = 0\n"],
+            parse_input,
+        );
+    }
+
+    #[test]
+    fn mask_matches_naive() {
+        let mut rng = Rng::new(14);
+        for _ in 0..500 {
+            let floating = rng.below(BITS + 1);
+            let mask_str = random_mask(&mut rng, floating);
+            let mask: Mask = mask_str.parse().unwrap();
+            let data = rng.next_u64() >> (64 - BITS);
+            assert_eq!(mask.mask(data), naive_mask(&mask_str, data));
+        }
+    }
+
+    #[test]
+    fn addresses_match_naive() {
+        let mut rng = Rng::new(14);
+        for _ in 0..500 {
+            let floating = rng.below(9);
+            let mask_str = random_mask(&mut rng, floating);
+            let mask: Mask = mask_str.parse().unwrap();
+            let addr = rng.next_u64() >> (64 - BITS);
+
+            let mut actual = mask.addresses(addr);
+            let mut expected = naive_addresses(&mask_str, addr);
+            actual.sort_unstable();
+            expected.sort_unstable();
+            assert_eq!(actual, expected, "{} {}", mask_str, addr);
+        }
+    }
+}
@@ -12,7 +12,7 @@ struct SpokenNumbers {
 impl SpokenNumbers {
     fn new(start_nums: Vec<usize>) -> SpokenNumbers {
         SpokenNumbers {
-            start_nums: start_nums,
+            start_nums,
             last_spoken: HashMap::new(),
             prev: None,
             idx: 0,
@@ -44,10 +44,16 @@ impl Iterator for SpokenNumbers {
     }
 }
 
+fn parse_input(input: &str) -> Result<Vec<usize>, &'static str> {
+    let line = input.lines().next().ok_or("No starting numbers")?;
+    line.split(',')
+        .map(|s| s.parse().or(Err("Invalid starting number")))
+        .collect()
+}
+
 fn main() {
     let input = get_input(15);
-    let line = input.lines().next().unwrap();
-    let start_nums: Vec<usize> = line.split(',').map(|s| s.parse().unwrap()).collect();
+    let start_nums = parse_input(&input).unwrap();
 
     dbg!(SpokenNumbers::new(start_nums.clone())
         .nth(2020 - 1)
@@ -56,3 +62,14 @@ fn main() {
         .nth(30000000 - 1)
         .unwrap());
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::fuzz::check_parser;
+
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(&["0,3,6\n"], parse_input);
+    }
+}
@@ -25,55 +25,84 @@ struct Info {
     tickets: Vec<Ticket>,
 }
 
-fn parse_ticket(s: &str) -> Ticket {
-    Ticket {
-        fields: s.split(',').map(|v| v.parse().unwrap()).collect(),
+fn parse_ticket(s: &str) -> Result<Ticket, &'static str> {
+    let fields = s
+        .split(',')
+        .map(|v| v.parse().or(Err("Invalid ticket field")))
+        .collect::<Result<_, _>>()?;
+    Ok(Ticket { fields })
+}
+
+fn parse_section<'a>(
+    group: Option<&[&'a str]>,
+    header: &str,
+) -> Result<Vec<&'a str>, &'static str> {
+    match group {
+        Some([first, rest @ ..]) if *first == header => Ok(rest.to_vec()),
+        _ => Err("Missing section header"),
     }
 }
 
-fn parse_input(input: &str) -> Info {
+fn parse_input(input: &str) -> Result<Info, &'static str> {
     let lines: Vec<_> = input.lines().collect();
     let mut groups = lines.split(|line| line.is_empty());
-    let props = groups.next().unwrap();
-    let my_ticket = &groups.next().unwrap()[1..];
-    let tickets = &groups.next().unwrap()[1..];
-    assert!(groups.next().is_none());
+    let props = groups.next().ok_or("No properties")?;
+    let my_ticket = parse_section(groups.next(), "your ticket:")?;
+    let tickets = parse_section(groups.next(), "nearby tickets:")?;
+    if groups.next().is_some() {
+        return Err("Trailing sections");
+    }
 
     let mut prop_map = PropMap::new();
-    for prop in props.iter().copied() {
+    for prop in props {
         let mut split = prop.split(": ");
-        let name = split.next().unwrap();
-        let value = split.next().unwrap();
-        assert!(split.next().is_none());
+        let name = split.next().ok_or("No property name")?;
+        let value = split.next().ok_or("No property value")?;
+        if split.next().is_some() {
+            return Err("Invalid property");
+        }
 
         let mut ranges = Vec::<PropRange>::new();
         for range_str in value.split(" or ") {
             let mut range_split = range_str.split('-');
-            let min: usize = range_split.next().unwrap().parse().unwrap();
-            let max: usize = range_split.next().unwrap().parse().unwrap();
-            assert!(range_split.next().is_none());
+            let min = range_split.next().ok_or("No range min")?;
+            let min: usize = min.parse().or(Err("Invalid range min"))?;
+            let max = range_split.next().ok_or("No range max")?;
+            let max: usize = max.parse().or(Err("Invalid range max"))?;
+            if range_split.next().is_some() {
+                return Err("Invalid range");
+            }
             ranges.push(min..=max);
         }
         prop_map.insert(name.to_owned(), ranges);
     }
 
-    Info {
+    let my_ticket = match &my_ticket[..] {
+        [ticket] => parse_ticket(ticket)?,
+        _ => return Err("Expected exactly one ticket of mine"),
+    };
+
+    Ok(Info {
         props: prop_map,
-        my_ticket: parse_ticket(my_ticket[0]),
-        tickets: tickets.iter().copied().map(parse_ticket).collect(),
-    }
+        my_ticket,
+        tickets: tickets
+            .iter()
+            .copied()
+            .map(parse_ticket)
+            .collect::<Result<_, _>>()?,
+    })
 }
 
 fn main() {
     let input = get_input(16);
-    let info = parse_input(&input);
+    let info = parse_input(&input).unwrap();
 
     let flat_props: Vec<_> = info.props.values().flatten().collect();
     let mut valid_tickets = Vec::<&Ticket>::new();
     let mut error_rate = 0usize;
     'outer: for ticket in &info.tickets {
         for field in &ticket.fields {
-            if flat_props.iter().copied().all(|p| !p.contains(&field)) {
+            if flat_props.iter().copied().all(|p| !p.contains(field)) {
                 error_rate += field;
                 continue 'outer;
             }
@@ -126,3 +155,28 @@ fn main() {
     }
     dbg!(product);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::fuzz::check_parser;
+
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(
+            &["class: 1-3 or 5-7\n\
+             row: 6-11 or 33-44\n\
+             seat: 13-40 or 45-50\n\
+             \n\
+             your ticket:\n\
+             7,1,14\n\
+             \n\
+             nearby tickets:\n\
+             7,3,47\n\
+             40,4,50\n\
+             55,2,20\n\
+             38,6,12\n"],
+            parse_input,
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use aoc2020::aoc_input::get_input;
+use aoc2020::coordinates::{BoxN, CoordN};
+use aoc2020::grid::Grid;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq)]
+enum GridPoint {
+    Active,
+    Inactive,
+}
+
+impl TryFrom<char> for GridPoint {
+    type Error = &'static str;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(GridPoint::Inactive),
+            '#' => Ok(GridPoint::Active),
+            _ => Err("Invalid char"),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct PocketDim<const N: usize> {
+    active: HashSet<CoordN<N>>,
+}
+
+impl<const N: usize> PocketDim<N> {
+    fn from_2d_initial_state(s: &str) -> Result<Self, &'static str> {
+        let grid: Grid<GridPoint> = s.parse()?;
+        let active = grid
+            .enumerate()
+            .filter(|(_, p)| **p == GridPoint::Active)
+            .map(|(c, _)| CoordN::from(c))
+            .collect();
+        Ok(PocketDim { active })
+    }
+
+    fn tick(&mut self) {
+        let mut candidates = HashSet::new();
+        for c in self.active.iter() {
+            candidates.extend(c.moore(1));
+        }
+
+        let mut new_active = HashSet::new();
+        for c in candidates.iter() {
+            let active = self.active.contains(c);
+            let neighbors_active = c.moore(1).filter(|n| self.active.contains(n)).count();
+
+            let next_active = matches!(
+                (active, neighbors_active),
+                (false, 3) | (true, 2) | (true, 3)
+            );
+            if next_active {
+                new_active.insert(*c);
+            }
+        }
+        self.active = new_active;
+    }
+
+    fn ticks(&mut self, n: usize) {
+        for _ in 0..n {
+            self.tick();
+        }
+    }
+
+    fn active_count(&self) -> usize {
+        self.active.len()
+    }
+}
+
+// Prints the active region one 2D slice at a time, in the puzzle's format
+impl<const N: usize> fmt::Display for PocketDim<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bounds = match BoxN::from_points(self.active.iter().copied()) {
+            None => return Ok(()),
+            Some(b) => b,
+        };
+
+        for c in bounds.coords() {
+            let (x, y) = (c.0[0], c.0[1]);
+            if x == bounds.min.0[0] && y == bounds.min.0[1] && N > 2 {
+                if c != bounds.min {
+                    writeln!(f)?;
+                }
+                let names = ["z", "w"];
+                let labels: Vec<_> = (2..N)
+                    .map(|i| format!("{}={}", names.get(i - 2).unwrap_or(&"?"), c.0[i]))
+                    .collect();
+                writeln!(f, "{}", labels.join(", "))?;
+            }
+
+            let ch = if self.active.contains(&c) { '#' } else { '.' };
+            write!(f, "{}", ch)?;
+            if x == bounds.max.0[0] {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn main() {
+    let input = get_input(17);
+    let mut pd = PocketDim::<3>::from_2d_initial_state(&input).unwrap();
+    pd.ticks(6);
+    dbg!(pd.active_count());
+
+    let mut pd = PocketDim::<4>::from_2d_initial_state(&input).unwrap();
+    pd.ticks(6);
+    dbg!(pd.active_count());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::fuzz::check_parser;
+
+    #[test]
+    fn test_display() {
+        let mut pd = PocketDim::<3>::from_2d_initial_state(".#.\n..#\n###\n").unwrap();
+        assert_eq!(pd.to_string(), "z=0\n.#.\n..#\n###\n");
+
+        pd.tick();
+        let expected = "z=-1\n#..\n..#\n.#.\n\nz=0\n#.#\n.##\n.#.\n\nz=1\n#..\n..#\n.#.\n";
+        assert_eq!(pd.to_string(), expected);
+    }
+
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(&[".#.\n..#\n###\n"], PocketDim::<3>::from_2d_initial_state);
+    }
+}
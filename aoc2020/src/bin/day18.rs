@@ -16,7 +16,7 @@ struct Lexer<'a> {
 }
 
 impl<'a> Lexer<'a> {
-    fn new(input: &str) -> Lexer {
+    fn new(input: &str) -> Lexer<'_> {
         Lexer {
             stream: input.chars().peekable(),
         }
@@ -120,36 +120,50 @@ impl<'a> Parser<'a> {
         }
     }
 
-    fn parse_expr_rparen(&mut self) -> AstNode {
-        let expr = self.parse_expr();
-        match self.tokens.next().unwrap() {
+    fn next_token(&mut self) -> Result<Token, &'static str> {
+        match self.tokens.next() {
+            None => Err("Unexpected end of input"),
+            Some(Token::Unexpected) | Some(Token::Overflow) => Err("Tokenization failed"),
+            Some(t) => Ok(t),
+        }
+    }
+
+    fn expect_end(&mut self) -> Result<(), &'static str> {
+        match self.tokens.next() {
+            None => Ok(()),
+            Some(_) => Err("Trailing tokens"),
+        }
+    }
+
+    fn parse_expr_rparen(&mut self) -> Result<AstNode, &'static str> {
+        let expr = self.parse_expr()?;
+        match self.next_token()? {
             Token::RParen => (),
-            _ => panic!("Syntax error"),
+            _ => return Err("Syntax error"),
         };
-        expr
+        Ok(expr)
     }
 
-    fn parse_term(&mut self) -> AstNode {
-        match self.tokens.next().unwrap() {
+    fn parse_term(&mut self) -> Result<AstNode, &'static str> {
+        match self.next_token()? {
             Token::LParen => self.parse_expr_rparen(),
-            Token::Num(n) => AstNode::Number(n),
-            Token::Unexpected | Token::Overflow => panic!("Tokenization failed"),
-            _ => panic!("Syntax error"),
+            Token::Num(n) => Ok(AstNode::Number(n)),
+            _ => Err("Syntax error"),
         }
     }
 
     fn peek_operator(&mut self) -> bool {
-        match self.tokens.peek() {
-            Some(&Token::Plus) | Some(&Token::Asterisk) => true,
-            _ => false,
-        }
+        matches!(
+            self.tokens.peek(),
+            Some(&Token::Plus) | Some(&Token::Asterisk)
+        )
     }
 
-    fn parse_expr(&mut self) -> AstNode {
-        let mut node = self.parse_term();
+    fn parse_expr(&mut self) -> Result<AstNode, &'static str> {
+        let mut node = self.parse_term()?;
         while self.peek_operator() {
             let operator = self.tokens.next().unwrap();
-            let rhs = Box::new(self.parse_term());
+            let rhs = Box::new(self.parse_term()?);
             let lhs = Box::new(node);
             node = match operator {
                 Token::Plus => AstNode::Add(lhs, rhs),
@@ -157,46 +171,46 @@ impl<'a> Parser<'a> {
                 _ => unreachable!(),
             };
         }
-        node
+        Ok(node)
     }
 
-    fn parse_expr2_rparen(&mut self) -> AstNode {
-        let expr = self.parse_expr2();
-        match self.tokens.next().unwrap() {
+    fn parse_expr2_rparen(&mut self) -> Result<AstNode, &'static str> {
+        let expr = self.parse_expr2()?;
+        match self.next_token()? {
             Token::RParen => (),
-            _ => panic!("Syntax error"),
+            _ => return Err("Syntax error"),
         };
-        expr
+        Ok(expr)
     }
 
-    fn parse_term2(&mut self) -> AstNode {
-        match self.tokens.next().unwrap() {
+    fn parse_term2(&mut self) -> Result<AstNode, &'static str> {
+        match self.next_token()? {
             Token::LParen => self.parse_expr2_rparen(),
-            Token::Num(n) => AstNode::Number(n),
-            _ => panic!("Syntax error"),
+            Token::Num(n) => Ok(AstNode::Number(n)),
+            _ => Err("Syntax error"),
         }
     }
 
-    fn parse_factor2(&mut self) -> AstNode {
-        let mut node = self.parse_term2();
+    fn parse_factor2(&mut self) -> Result<AstNode, &'static str> {
+        let mut node = self.parse_term2()?;
         while let Some(&Token::Plus) = self.tokens.peek() {
             self.tokens.next().unwrap();
-            let rhs = Box::new(self.parse_term2());
+            let rhs = Box::new(self.parse_term2()?);
             let lhs = Box::new(node);
             node = AstNode::Add(lhs, rhs);
         }
-        node
+        Ok(node)
     }
 
-    fn parse_expr2(&mut self) -> AstNode {
-        let mut node = self.parse_factor2();
+    fn parse_expr2(&mut self) -> Result<AstNode, &'static str> {
+        let mut node = self.parse_factor2()?;
         while let Some(&Token::Asterisk) = self.tokens.peek() {
             self.tokens.next().unwrap();
-            let rhs = Box::new(self.parse_factor2());
+            let rhs = Box::new(self.parse_factor2()?);
             let lhs = Box::new(node);
             node = AstNode::Multiply(lhs, rhs);
         }
-        node
+        Ok(node)
     }
 }
 
@@ -208,22 +222,46 @@ fn eval(node: &AstNode) -> u64 {
     }
 }
 
+fn parse_line(line: &str) -> Result<(AstNode, AstNode), &'static str> {
+    let mut parser = Parser::new(Lexer::new(line));
+    let expr1 = parser.parse_expr()?;
+    parser.expect_end()?;
+
+    let mut parser = Parser::new(Lexer::new(line));
+    let expr2 = parser.parse_expr2()?;
+    parser.expect_end()?;
+
+    Ok((expr1, expr2))
+}
+
+fn parse_input(input: &str) -> Result<Vec<(AstNode, AstNode)>, &'static str> {
+    input.lines().map(parse_line).collect()
+}
+
 fn main() {
     let input = get_input(18);
+    let exprs = parse_input(&input).unwrap();
 
     let mut total1 = 0u64;
     let mut total2 = 0u64;
-    for line in input.lines() {
-        let lexer = Lexer::new(line);
-        let mut parser = Parser::new(lexer);
-        let expr = parser.parse_expr();
-        total1 += eval(&expr);
-
-        let lexer = Lexer::new(line);
-        let mut parser = Parser::new(lexer);
-        let expr = parser.parse_expr2();
-        total2 += eval(&expr);
+    for (expr1, expr2) in &exprs {
+        total1 += eval(expr1);
+        total2 += eval(expr2);
     }
     dbg!(total1);
     dbg!(total2);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::fuzz::check_parser;
+
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(
+            &["1 + (2 * 3) + (4 * (5 + 6))\n((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2\n"],
+            parse_input,
+        );
+    }
+}
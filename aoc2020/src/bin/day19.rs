@@ -10,37 +10,67 @@ enum Rule {
 
 type Rules = HashMap<usize, Rule>;
 
-fn parse_rule(s: &str) -> Rule {
-    match s.chars().next().unwrap() {
+fn parse_rule(s: &str) -> Result<Rule, &'static str> {
+    match s.chars().next().ok_or("Empty rule")? {
         '"' => {
-            let ch = &s[1..s.len() - 1];
-            assert!(ch.len() == 1);
-            Rule::Term(ch.chars().next().unwrap())
+            let ch = s
+                .strip_prefix('"')
+                .and_then(|s| s.strip_suffix('"'))
+                .ok_or("Unterminated terminal")?;
+            let mut chars = ch.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok(Rule::Term(c)),
+                _ => Err("Terminal must be a single character"),
+            }
         }
         _ => {
             let mut or = Vec::new();
             for sub in s.split(" | ") {
-                let indices: Vec<usize> = sub.split(' ').map(|s| s.parse().unwrap()).collect();
+                let indices = sub
+                    .split(' ')
+                    .map(|s| s.parse().or(Err("Invalid rule index")))
+                    .collect::<Result<Vec<usize>, _>>()?;
                 or.push(indices);
             }
-            Rule::Or(or)
+            Ok(Rule::Or(or))
         }
     }
 }
 
-fn parse_rules(lines: &[&str]) -> Rules {
+fn parse_rules(lines: &[&str]) -> Result<Rules, &'static str> {
     let mut rules = HashMap::new();
     for line in lines {
         let mut split = line.split(": ");
-        let idx = split.next().unwrap();
-        let rule = split.next().unwrap();
-        assert!(split.next().is_none());
+        let idx = split.next().ok_or("No rule index")?;
+        let rule = split.next().ok_or("No rule body")?;
+        if split.next().is_some() {
+            return Err("Invalid rule");
+        }
 
-        let idx = idx.parse::<usize>().unwrap();
-        let rule = parse_rule(rule);
+        let idx = idx.parse::<usize>().or(Err("Invalid rule index"))?;
+        let rule = parse_rule(rule)?;
         rules.insert(idx, rule);
     }
-    rules
+
+    for rule in rules.values() {
+        if let Rule::Or(or) = rule {
+            if or.iter().flatten().any(|idx| !rules.contains_key(idx)) {
+                return Err("Reference to undefined rule");
+            }
+        }
+    }
+
+    Ok(rules)
+}
+
+fn parse_input(input: &str) -> Result<(Rules, Vec<&str>), &'static str> {
+    let lines: Vec<_> = input.lines().collect();
+    let groups: Vec<_> = lines.split(|line| line.is_empty()).collect();
+    let (rules, messages) = match &groups[..] {
+        [rules, messages] => (rules, messages),
+        _ => return Err("Expected rules and messages sections"),
+    };
+    Ok((parse_rules(rules)?, messages.to_vec()))
 }
 
 fn build_regex_recurse(rules: &Rules, idx: usize) -> String {
@@ -65,9 +95,9 @@ fn build_regex(rules: &Rules) -> String {
 }
 
 fn solve_part1(rules: &Rules, messages: &[&str]) {
-    let regex_str = build_regex(&rules);
+    let regex_str = build_regex(rules);
     let regex = Regex::new(&regex_str).unwrap();
-    let count = messages.iter().filter(|m| regex.is_match(*m)).count();
+    let count = messages.iter().filter(|m| regex.is_match(m)).count();
     dbg!(count);
 }
 
@@ -126,13 +156,31 @@ fn solve_part2(rules: &Rules, messages: &[&str]) {
 
 fn main() {
     let input = get_input(19);
-    let lines: Vec<_> = input.lines().collect();
+    let (rules, messages) = parse_input(&input).unwrap();
 
-    let groups: Vec<_> = lines.split(|line| line.is_empty()).collect();
-    assert_eq!(groups.len(), 2);
-    let rules = parse_rules(groups[0]);
-    let messages = groups[1];
+    solve_part1(&rules, &messages);
+    solve_part2(&rules, &messages);
+}
 
-    solve_part1(&rules, messages);
-    solve_part2(&rules, messages);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::fuzz::check_parser;
+
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(
+            &["0: 4 1 5\n\
+               1: 2 3 | 3 2\n\
+               2: 4 4 | 5 5\n\
+               3: 4 5 | 5 4\n\
+               4: \"a\"\n\
+               5: \"b\"\n\
+               \n\
+               ababbb\n\
+               bababa\n\
+               abbbab\n"],
+            |s| parse_input(s).map(|(rules, _)| rules),
+        );
+    }
 }
@@ -40,22 +40,29 @@ impl FromStr for Policy {
 
         let min = min.parse::<usize>().or(Err("Bad policy range min"))?;
         let max = max.parse::<usize>().or(Err("Bad policy range max"))?;
+        if min == 0 || min > max {
+            return Err("Bad policy range");
+        }
         Ok(Policy { min, max, ch })
     }
 }
 
-fn parse_line(s: &str) -> (Policy, &str) {
+fn parse_line(s: &str) -> Result<(Policy, &str), &'static str> {
     let parts: Vec<_> = s.split(": ").collect();
     let (policy, password) = match &parts[..] {
         [policy, password] => (*policy, *password),
-        _ => panic!("Invalid line"),
+        _ => return Err("Invalid line"),
     };
-    (policy.parse().expect("Error parsing policy"), password)
+    Ok((policy.parse()?, password))
+}
+
+fn parse_input(input: &str) -> Result<Vec<(Policy, &str)>, &'static str> {
+    input.lines().map(parse_line).collect()
 }
 
 fn main() {
     let input = get_input(2);
-    let entries: Vec<_> = input.lines().map(parse_line).collect();
+    let entries = parse_input(&input).unwrap();
 
     let valid1 = entries
         .iter()
@@ -69,3 +76,16 @@ fn main() {
         .count();
     dbg!(valid2);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::fuzz::check_parser;
+
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(&["1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n"], |s| {
+            parse_input(s).map(|v| v.len())
+        });
+    }
+}
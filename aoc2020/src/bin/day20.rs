@@ -1,6 +1,7 @@
 use aoc2020::aoc_input::get_input;
-use aoc2020::coordinates::{Coord, Delta};
-use aoc2020::grid::{Axis, Grid, Rotation};
+use aoc2020::coordinates::{Coord, Delta, Rect};
+use aoc2020::grid::{Axis, Grid, GridView, Rotation};
+use aoc2020::symmetry::D4;
 use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone)]
@@ -11,11 +12,13 @@ struct Tile {
     right: String,
     bottom: String,
     interior: Grid<char>,
+    // Applied lazily through a view, so searching orientations doesn't copy
+    // the interior. It's only copied when the image is assembled.
+    orientation: D4,
 }
 
 impl Tile {
     fn flip_inplace(&mut self, axis: Axis) {
-        self.interior.flip_inplace(axis);
         match axis {
             Axis::Horizontal => {
                 std::mem::swap(&mut self.top, &mut self.bottom);
@@ -31,7 +34,6 @@ impl Tile {
     }
 
     fn rotate_clockwise_inplace(&mut self) {
-        self.interior.rotate_clockwise_inplace();
         std::mem::swap(&mut self.top, &mut self.right);
         std::mem::swap(&mut self.left, &mut self.top);
         std::mem::swap(&mut self.bottom, &mut self.left);
@@ -39,56 +41,44 @@ impl Tile {
         self.bottom = self.bottom.chars().rev().collect();
     }
 
-    fn rotate_inplace(&mut self, rotation: Rotation) {
-        for _ in 0..rotation.to_cw_count() {
+    fn transform_inplace(&mut self, t: D4) {
+        self.orientation = self.orientation.then(t);
+        if t.is_flipped() {
+            self.flip_inplace(Axis::Horizontal);
+        }
+        for _ in 0..t.cw_count() {
             self.rotate_clockwise_inplace();
         }
     }
 
-    fn faces<'a>(&'a self) -> [&'a String; 4] {
-        return [&self.top, &self.right, &self.bottom, &self.left];
-    }
-}
-
-fn parse_tile(lines: &[&str]) -> Tile {
-    let right = lines[0].split(' ').nth(1).unwrap();
-    let right = &right[..right.find(':').unwrap()];
-    let id: usize = right.parse().unwrap();
-    let grid_lines = &lines[1..];
-    let full_grid: Grid<char> = grid_lines.join("\n").parse().unwrap();
-
-    let w = full_grid.width() as isize;
-    let h = full_grid.height() as isize;
-
-    let mut top = String::with_capacity(w as usize);
-    let mut bottom = String::with_capacity(w as usize);
-    for x in 0..w {
-        top.push(*full_grid.get(Coord(x, 0)).unwrap());
-        bottom.push(*full_grid.get(Coord(x, h - 1)).unwrap());
+    fn interior(&self) -> GridView<'_, char> {
+        self.interior.view().transformed(self.orientation)
     }
 
-    let mut left = String::with_capacity(h as usize);
-    let mut right = String::with_capacity(h as usize);
-    for y in 0..h {
-        left.push(*full_grid.get(Coord(0, y)).unwrap());
-        right.push(*full_grid.get(Coord(w - 1, y)).unwrap());
+    fn faces(&self) -> [&String; 4] {
+        [&self.top, &self.right, &self.bottom, &self.left]
     }
+}
 
-    let interior_lines: Vec<_> = grid_lines[1..grid_lines.len() - 1]
-        .iter()
-        .copied()
-        .map(|line| &line[1..line.len() - 1])
-        .collect();
-    let interior: Grid<char> = interior_lines.join("\n").parse().unwrap();
-
-    Tile {
+fn parse_tile(lines: &[&str]) -> Result<Tile, &'static str> {
+    let (header, grid_lines) = lines.split_first().ok_or("Empty tile")?;
+    let id = header
+        .strip_prefix("Tile ")
+        .and_then(|s| s.strip_suffix(':'))
+        .ok_or("Invalid tile header")?;
+    let id: usize = id.parse().or(Err("Invalid tile id"))?;
+    let full_grid: Grid<char> = grid_lines.join("\n").parse()?;
+    let interior = full_grid.crop_border(1).ok_or("Tile too small")?;
+
+    Ok(Tile {
         id,
-        top,
-        bottom,
-        left,
-        right,
+        top: full_grid.top_border().collect(),
+        bottom: full_grid.bottom_border().collect(),
+        left: full_grid.left_border().collect(),
+        right: full_grid.right_border().collect(),
         interior,
-    }
+        orientation: D4::IDENTITY,
+    })
 }
 
 struct ArrangeCtx {
@@ -96,8 +86,6 @@ struct ArrangeCtx {
     face_map: HashMap<String, Vec<usize>>,
     tile_locs: HashMap<Coord, usize>,
     tile_locs_rev: HashMap<usize, Coord>,
-    tile_locs_xmax: isize,
-    tile_locs_ymax: isize,
 }
 
 #[derive(Debug, Clone)]
@@ -121,7 +109,7 @@ impl ArrangeCtx {
 
         let mut face_map = HashMap::<String, Vec<usize>>::new();
         for tile in tile_map.values() {
-            for f in tile.faces().iter().copied() {
+            for f in tile.faces() {
                 let rev = f.chars().rev().collect::<String>();
                 face_map.entry(f.clone()).or_default().push(tile.id);
                 face_map.entry(rev).or_default().push(tile.id);
@@ -133,8 +121,6 @@ impl ArrangeCtx {
             face_map,
             tile_locs: HashMap::new(),
             tile_locs_rev: HashMap::new(),
-            tile_locs_xmax: 0,
-            tile_locs_ymax: 0,
         }
     }
 
@@ -167,8 +153,10 @@ impl ArrangeCtx {
     fn set_tile_loc(&mut self, id: usize, coord: Coord) {
         self.tile_locs.insert(coord, id);
         self.tile_locs_rev.insert(id, coord);
-        self.tile_locs_xmax = self.tile_locs_xmax.max(coord.0);
-        self.tile_locs_ymax = self.tile_locs_ymax.max(coord.1);
+    }
+
+    fn tile_bounds(&self) -> Rect {
+        Rect::from_points(self.tile_locs.keys().copied()).unwrap()
     }
 
     fn get_tile_by_loc(&self, coord: Coord) -> &Tile {
@@ -186,16 +174,16 @@ impl ArrangeCtx {
             (false, false) => Rotation::Cw180,
             (true, false) => Rotation::Cw270,
         };
-        drop(tile);
-        self.tile_map.get_mut(&id).unwrap().rotate_inplace(rotation);
+        let tile = self.tile_map.get_mut(&id).unwrap();
+        tile.transform_inplace(rotation.into());
         self.set_tile_loc(id, Coord::origin());
     }
 
     fn next_bfs_layer(layer: HashSet<Coord>) -> HashSet<Coord> {
         let mut next_layer = HashSet::new();
         for c in layer {
-            next_layer.insert(Coord(c.0 + 1, c.1));
-            next_layer.insert(Coord(c.0, c.1 + 1));
+            next_layer.insert(c + Delta(1, 0));
+            next_layer.insert(c + Delta(0, 1));
         }
         next_layer
     }
@@ -203,11 +191,11 @@ impl ArrangeCtx {
     fn has_boundary(&self, id: usize) -> bool {
         let tile = self.tile_map.get(&id).unwrap();
         let faces = &[&tile.left, &tile.top, &tile.right, &tile.bottom];
-        faces.iter().any(|f| self.is_boundary(*f))
+        faces.iter().any(|f| self.is_boundary(f))
     }
 
     fn find_tile_for_loc(&mut self, c: Coord) -> Option<(usize, Constraints)> {
-        let top = match self.tile_locs.get(&Coord(c.0, c.1 - 1)) {
+        let top = match self.tile_locs.get(&(c - Delta(0, 1))) {
             None => Constraint::Boundary,
             Some(tile_id) => {
                 let tile = self.tile_map.get(tile_id).unwrap();
@@ -215,7 +203,7 @@ impl ArrangeCtx {
             }
         };
 
-        let left = match self.tile_locs.get(&Coord(c.0 - 1, c.1)) {
+        let left = match self.tile_locs.get(&(c - Delta(1, 0))) {
             None => Constraint::Boundary,
             Some(tile_id) => {
                 let tile = self.tile_map.get(tile_id).unwrap();
@@ -271,21 +259,19 @@ impl ArrangeCtx {
             Some(t) => t,
         };
 
-        let mut count = 0;
-        while !self.check_constraints(id, &constraints) {
-            assert_ne!(count, 8);
+        // Undo the previous attempt along with applying the next one
+        let mut applied = D4::IDENTITY;
+        for &t in &D4::ALL {
             let tile = self.tile_map.get_mut(&id).unwrap();
+            tile.transform_inplace(applied.inverse().then(t));
+            applied = t;
 
-            tile.rotate_clockwise_inplace();
-            count += 1;
-
-            if count == 4 {
-                tile.flip_inplace(Axis::Horizontal);
+            if self.check_constraints(id, &constraints) {
+                self.set_tile_loc(id, c);
+                return true;
             }
         }
-
-        self.set_tile_loc(id, c);
-        true
+        panic!("No orientation of tile {} fits at {:?}", id, c);
     }
 
     fn solve_puzzle(&mut self) {
@@ -313,106 +299,43 @@ impl ArrangeCtx {
     }
 
     fn corner_product(&self) -> usize {
-        let left = 0isize;
-        let right = self.tile_locs_xmax;
-        let top = 0isize;
-        let bottom = self.tile_locs_ymax;
-        let corners = [
-            Coord(left, top),
-            Coord(right, top),
-            Coord(left, bottom),
-            Coord(right, bottom),
-        ];
-
-        corners
+        self.tile_bounds()
+            .corners()
             .iter()
             .map(|c| self.tile_locs.get(c).unwrap())
             .product()
     }
 
-    fn tile_interior_dims(&self) -> (usize, usize) {
-        let tile = self.get_tile_by_loc(Coord::origin());
-        (tile.interior.width(), tile.interior.height())
-    }
-
     fn image(&self) -> Grid<char> {
-        let (tw, th) = self.tile_interior_dims();
-        let tile_count = (self.tile_locs_xmax + 1) * (self.tile_locs_ymax + 1);
-        let capacity = tw * th * (tile_count as usize);
-
-        let mut s = String::with_capacity(capacity);
-        for outer_y in 0..=self.tile_locs_ymax {
-            for inner_y in 0..th as isize {
-                for outer_x in 0..=self.tile_locs_xmax {
-                    for inner_x in 0..tw as isize {
-                        let tile_coord = Coord(outer_x, outer_y);
-                        let tile = self.get_tile_by_loc(tile_coord);
-                        let interior_coord = Coord(inner_x, inner_y);
-                        let ch = tile.interior.get(interior_coord);
-                        s.push(*ch.unwrap());
-                    }
-                }
-                s.push('\n');
-            }
-        }
-
-        s.parse().unwrap()
-    }
-}
-
-fn get_mask_deltas() -> Vec<Delta> {
-    let mask_str = "                  # \n#    ##    ##    ###\n #  #  #  #  #  #   ";
-    let mask: Grid<char> = mask_str.parse().unwrap();
-
-    let mut deltas = Vec::new();
-    for y in 0..mask.height() as isize {
-        for x in 0..mask.width() as isize {
-            if *mask.get(Coord(x, y)).unwrap() == '#' {
-                deltas.push(Delta(x, y));
-            }
-        }
+        let bounds = self.tile_bounds();
+        let tiles = Grid::from_fn(bounds.width(), bounds.height(), |c| {
+            let tile = self.get_tile_by_loc(bounds.min + (c - Coord::origin()));
+            tile.interior().to_owned()
+        });
+        Grid::from_tiles(tiles).unwrap()
     }
-    deltas
 }
 
-fn image_mask_remove(image: &mut Grid<char>, mask: &[Delta]) -> bool {
-    let mut ret = false;
-
-    for y in 0..image.height() as isize {
-        for x in 0..image.width() as isize {
-            let base = Coord(x, y);
-            if !mask.iter().all(|d| image.get(base + *d) == Some(&'#')) {
-                continue;
-            }
-
-            for d in mask {
-                *image.get_mut(base + *d).unwrap() = ' ';
-            }
-            ret = true;
-        }
-    }
-
-    ret
-}
-
-fn mask_out_image(mut image: &mut Grid<char>) {
-    let mut symmetry_count = 0;
-    let mask_deltas = get_mask_deltas();
-
-    while !image_mask_remove(&mut image, &mask_deltas) {
-        assert_ne!(symmetry_count, 8);
-
-        image.rotate_clockwise_inplace();
-        symmetry_count += 1;
-
-        if symmetry_count == 4 {
-            image.flip_inplace(Axis::Horizontal);
-        }
-    }
+const SEA_MONSTER: &str = "                  # \n#    ##    ##    ###\n #  #  #  #  #  #   ";
+
+fn mask_out_image(image: &mut Grid<char>) {
+    let monster: Grid<char> = SEA_MONSTER.parse().unwrap();
+    let matches = image.find_pattern(&monster, &' ');
+    // Matches come in the order of D4::ALL, so this is the first orientation
+    // with monsters. Stray matches in other orientations are ignored.
+    let orientation = match matches.first() {
+        None => panic!("No sea monsters in any orientation"),
+        Some(m) => m.orientation,
+    };
+    let matches: Vec<_> = matches
+        .into_iter()
+        .filter(|m| m.orientation == orientation)
+        .collect();
+    image.replace_matches(&matches, 'O');
 }
 
-fn parse_input(input: &str) -> Vec<Tile> {
-    let lines: Vec<_> = input[..input.len() - 1].lines().collect();
+fn parse_input(input: &str) -> Result<Vec<Tile>, &'static str> {
+    let lines: Vec<_> = input.trim_end().lines().collect();
     let groups: Vec<_> = lines.split(|line| line.is_empty()).collect();
     groups.iter().copied().map(parse_tile).collect()
 }
@@ -420,7 +343,7 @@ fn parse_input(input: &str) -> Vec<Tile> {
 fn main() {
     let input = get_input(20);
 
-    let tiles = parse_input(&input);
+    let tiles = parse_input(&input).unwrap();
     let mut ctx = ArrangeCtx::new(tiles);
     ctx.solve_puzzle();
     dbg!(ctx.corner_product());
@@ -436,9 +359,12 @@ mod tests {
     use std::assert_eq;
 
     use super::*;
+    use aoc2020::fuzz::check_parser;
+    use aoc2020::generators::jigsaw_tiles;
+    use aoc2020::rng::Rng;
 
     fn test_input() -> ArrangeCtx {
-        let tiles = parse_input(include_str!("day20_test_tiles.txt"));
+        let tiles = parse_input(include_str!("day20_test_tiles.txt")).unwrap();
         ArrangeCtx::new(tiles)
     }
 
@@ -453,26 +379,38 @@ mod tests {
         assert_eq!(ctx.corner_product(), 20899048083289);
     }
 
+    fn assert_symmetric(expected_image: &Grid<char>, actual_image: Grid<char>) {
+        assert!(D4::ALL.iter().any(|&t| {
+            let mut image = actual_image.clone();
+            image.transform_inplace(t);
+            *expected_image == image
+        }));
+    }
+
     #[test]
     fn test_arrangement() {
         let mut ctx = test_input();
         ctx.solve_puzzle();
-        let mut actual_image = ctx.image();
-        let expected_image = test_image();
-
-        let mut symmetry_count = 0;
-        while expected_image != actual_image {
-            assert_ne!(symmetry_count, 8);
-
-            actual_image.rotate_clockwise_inplace();
-            symmetry_count += 1;
+        assert_symmetric(&test_image(), ctx.image());
+    }
 
-            if symmetry_count == 4 {
-                actual_image.flip_inplace(Axis::Horizontal);
-            }
+    #[test]
+    fn generated_jigsaws() {
+        let mut rng = Rng::new(20);
+        for side in 2..6 {
+            let jigsaw = jigsaw_tiles(&mut rng, side, 10);
+            let mut ctx = ArrangeCtx::new(parse_input(&jigsaw.input).unwrap());
+            ctx.solve_puzzle();
+            assert_eq!(ctx.corner_product(), jigsaw.corner_ids.iter().product());
+            assert_symmetric(&jigsaw.image, ctx.image());
         }
     }
 
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(&[include_str!("day20_test_tiles.txt")], parse_input);
+    }
+
     #[test]
     fn check_mask_out_image() {
         let mut image = test_image();
@@ -16,7 +16,7 @@ impl FromStr for Food {
         let mut split = trim.split(" (contains ");
         let ingredients = split.next().ok_or("No ingredients")?;
         let allergens = split.next().ok_or("No allergens")?;
-        if !split.next().is_none() {
+        if split.next().is_some() {
             return Err("Trailing garbage");
         }
 
@@ -65,9 +65,13 @@ fn match_allergens(foods: &[Food]) -> HashMap<String, String> {
     assignments
 }
 
+fn parse_input(input: &str) -> Result<Vec<Food>, &'static str> {
+    input.lines().map(|line| line.parse()).collect()
+}
+
 fn main() {
     let input = get_input(21);
-    let foods: Vec<Food> = input.lines().map(|line| line.parse().unwrap()).collect();
+    let foods = parse_input(&input).unwrap();
     let assignments = match_allergens(&foods);
 
     let mut no_allergen_count = 0;
@@ -86,3 +90,20 @@ fn main() {
     let dangerous = dangerous.join(",");
     dbg!(dangerous);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::fuzz::check_parser;
+
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(
+            &["mxmxvkd kfcds sqjhc nhms (contains dairy, fish)\n\
+             trh fvjkl sbzzf mxmxvkd (contains dairy)\n\
+             sqjhc fvjkl (contains soy)\n\
+             sqjhc mxmxvkd sbzzf (contains fish)\n"],
+            parse_input,
+        );
+    }
+}
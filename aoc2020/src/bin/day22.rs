@@ -47,19 +47,33 @@ impl Combat {
     }
 }
 
-fn parse_deck(lines: &[&str]) -> Deck {
-    lines[1..].iter().map(|s| s.parse().unwrap()).collect()
+fn parse_deck(lines: &[&str], header: &str) -> Result<Deck, &'static str> {
+    match lines.split_first() {
+        Some((first, cards)) if *first == header => cards
+            .iter()
+            .map(|s| s.parse().or(Err("Invalid card")))
+            .collect(),
+        _ => Err("Missing player header"),
+    }
 }
 
-fn parse_combat_decks(input: &str) -> (Deck, Deck) {
+fn parse_combat_decks(input: &str) -> Result<(Deck, Deck), &'static str> {
     let lines: Vec<_> = input.lines().collect();
     let groups: Vec<_> = lines.split(|line| line.is_empty()).collect();
-    assert_eq!(groups.len(), 2);
+    if groups.len() != 2 {
+        return Err("Expected exactly two decks");
+    }
 
-    let player1 = parse_deck(groups[0]);
-    let player2 = parse_deck(groups[1]);
+    let player1 = parse_deck(groups[0], "Player 1:")?;
+    let player2 = parse_deck(groups[1], "Player 2:")?;
+
+    let mut cards: Vec<_> = player1.iter().chain(player2.iter()).collect();
+    cards.sort_unstable();
+    if cards.windows(2).any(|w| w[0] == w[1]) {
+        return Err("Duplicate card");
+    }
 
-    (player1, player2)
+    Ok((player1, player2))
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -146,7 +160,7 @@ fn score(winner: &Deck) -> usize {
 
 fn main() {
     let input = get_input(22);
-    let (player1, player2) = parse_combat_decks(&input);
+    let (player1, player2) = parse_combat_decks(&input).unwrap();
 
     let mut combat = Combat::new(player1.clone(), player2.clone());
     combat.finish();
@@ -156,3 +170,17 @@ fn main() {
     recursive_combat.finish();
     dbg!(score(recursive_combat.winner().unwrap()));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::fuzz::check_parser;
+
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(
+            &["Player 1:\n9\n2\n6\n3\n1\n\nPlayer 2:\n5\n8\n4\n7\n10\n"],
+            parse_combat_decks,
+        );
+    }
+}
@@ -3,7 +3,7 @@ use aoc2020::aoc_input::get_input;
 #[derive(Debug, Clone)]
 struct Game {
     cups: Vec<usize>,
-    current: usize
+    current: usize,
 }
 
 impl Game {
@@ -16,12 +16,15 @@ impl Game {
             cups[w[0]] = w[1];
         }
         cups[*cup_labels.last().unwrap()] = cup_labels[0];
-        Self { cups, current: cup_labels[0] }
+        Self {
+            cups,
+            current: cup_labels[0],
+        }
     }
 
     fn next_destination(&self, label: usize) -> usize {
         if label == 1 {
-            (self.cups.len() - 1) as usize
+            self.cups.len() - 1
         } else {
             label - 1
         }
@@ -73,16 +76,28 @@ impl Game {
     }
 }
 
-fn parse_cups(input: &str) -> Vec<usize> {
-    input
+fn parse_cups(input: &str) -> Result<Vec<usize>, &'static str> {
+    let cups = input
         .chars()
-        .map(|c| c.to_digit(10).unwrap() as usize)
-        .collect()
+        .map(|c| {
+            c.to_digit(10)
+                .map(|d| d as usize)
+                .ok_or("Invalid cup label")
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Labels must be exactly 1..=n for Game's successor array
+    let mut sorted = cups.clone();
+    sorted.sort_unstable();
+    if sorted.is_empty() || sorted.iter().copied().ne(1..=cups.len()) {
+        return Err("Cup labels must be a permutation of 1..=n");
+    }
+    Ok(cups)
 }
 
 fn main() {
     let input = get_input(23);
-    let orig_cups = parse_cups(input.trim());
+    let orig_cups = parse_cups(input.trim()).unwrap();
 
     let mut game = Game::new(orig_cups.as_slice());
     game.do_moves(100);
@@ -103,12 +118,67 @@ mod tests {
     use std::assert_eq;
 
     use super::*;
+    use aoc2020::fuzz::check_parser;
+    use aoc2020::rng::Rng;
+
+    // Keeps the current cup at the front of the circle and moves cups around
+    // physically, exactly as the puzzle describes.
+    fn naive_order_after_1(cup_labels: &[usize], moves: usize) -> Vec<usize> {
+        let max = cup_labels.len();
+        let prev = |label: usize| if label == 1 { max } else { label - 1 };
+
+        let mut circle = cup_labels.to_vec();
+        for _ in 0..moves {
+            let current = circle[0];
+            let pickup: Vec<_> = circle.drain(1..4).collect();
+            let mut destination = prev(current);
+            while pickup.contains(&destination) {
+                destination = prev(destination);
+            }
+            let pos = circle.iter().position(|&c| c == destination).unwrap();
+            circle.splice(pos + 1..pos + 1, pickup);
+            circle.rotate_left(1);
+        }
+
+        let one = circle.iter().position(|&c| c == 1).unwrap();
+        circle.rotate_left(one);
+        circle[1..].to_vec()
+    }
+
+    fn order_after_1(game: &Game) -> Vec<usize> {
+        let mut order = Vec::new();
+        let mut cur = game.cups[1];
+        while cur != 1 {
+            order.push(cur);
+            cur = game.cups[cur];
+        }
+        order
+    }
 
     #[test]
     fn test_labels_after_1() {
         let input = "389125467";
-        let mut game = Game::new(parse_cups(&input).as_slice());
+        let mut game = Game::new(parse_cups(input).unwrap().as_slice());
         game.do_moves(100);
         assert_eq!(game.labels_after_1(), "67384529");
     }
+
+    #[test]
+    fn moves_match_naive_simulation() {
+        let mut rng = Rng::new(23);
+        for len in 5..=20 {
+            let mut labels: Vec<usize> = (1..=len).collect();
+            rng.shuffle(&mut labels);
+            let moves = rng.below(200);
+
+            let mut game = Game::new(&labels);
+            game.do_moves(moves);
+            assert_eq!(order_after_1(&game), naive_order_after_1(&labels, moves));
+        }
+    }
+
+    #[test]
+    fn fuzz_parse_cups() {
+        check_parser(&["389125467"], parse_cups);
+    }
 }
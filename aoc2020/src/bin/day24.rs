@@ -1,32 +1,10 @@
 use aoc2020::aoc_input::get_input;
-use aoc2020::coordinates::{Coord, Delta};
+use aoc2020::coordinates::hex::{parse_path, Hex, HexDelta, PointyDir};
 use std::collections::HashMap;
 
-#[derive(Debug, Clone, Copy)]
-enum HexDir {
-    East,
-    SouthEast,
-    SouthWest,
-    West,
-    NorthWest,
-    NorthEast,
-}
-
-impl From<HexDir> for Delta {
-    fn from(dir: HexDir) -> Self {
-        match dir {
-            HexDir::East => Delta(1, 0),
-            HexDir::SouthEast => Delta(1, -1),
-            HexDir::SouthWest => Delta(0, -1),
-            HexDir::West => Delta(-1, 0),
-            HexDir::NorthEast => Delta(0, 1),
-            HexDir::NorthWest => Delta(-1, 1),
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 enum Color {
+    #[default]
     White,
     Black,
 }
@@ -44,69 +22,34 @@ impl Color {
     }
 }
 
-impl Default for Color {
-    fn default() -> Self {
-        Color::White
-    }
-}
-
-type TileMap = HashMap<Coord, Color>;
-
-fn parse_line(line: &str) -> Vec<HexDir> {
-    let mut res = Vec::new();
-    let mut chars = line.chars();
-    while let Some(c) = chars.next() {
-        let dir = match c {
-            'e' => HexDir::East,
-            'w' => HexDir::West,
-            's' => match chars.next().expect("Missing second character") {
-                'e' => HexDir::SouthEast,
-                'w' => HexDir::SouthWest,
-                _ => panic!("Invalid character"),
-            },
-            'n' => match chars.next().expect("Missing second character") {
-                'e' => HexDir::NorthEast,
-                'w' => HexDir::NorthWest,
-                _ => panic!("Invalid character"),
-            },
-            _ => panic!("Invalid character"),
-        };
-        res.push(dir);
-    }
-    res
-}
+type TileMap = HashMap<Hex, Color>;
 
-fn traverse_directions(dirs: &Vec<HexDir>) -> Coord {
-    dirs.iter()
-        .fold(Coord::origin(), |acc, &dir| acc + Delta::from(dir))
+fn traverse_directions(dirs: &[PointyDir]) -> Hex {
+    Hex::origin() + dirs.iter().map(|&dir| HexDelta::from(dir)).sum()
 }
 
-fn parse_input(input: &str) -> Vec<Coord> {
-    let tiles_directions: Vec<_> = input.lines().map(parse_line).collect();
-    tiles_directions.iter().map(traverse_directions).collect()
+fn parse_input(input: &str) -> Result<Vec<Hex>, &'static str> {
+    let tiles_directions = input
+        .lines()
+        .map(parse_path)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(tiles_directions
+        .iter()
+        .map(|d| traverse_directions(d))
+        .collect())
 }
 
-fn flip_tiles(coords: &[Coord]) -> TileMap {
-    let mut tiles = HashMap::<Coord, Color>::new();
+fn flip_tiles(coords: &[Hex]) -> TileMap {
+    let mut tiles = HashMap::<Hex, Color>::new();
     for c in coords {
         tiles.entry(*c).or_default().flip_inplace()
     }
     tiles
 }
 
-const DIRS: [HexDir; 6] = [
-    HexDir::East,
-    HexDir::SouthEast,
-    HexDir::SouthWest,
-    HexDir::West,
-    HexDir::NorthWest,
-    HexDir::NorthEast,
-];
-
-fn adjacent_black_tiles_count(tiles: &TileMap, loc: &Coord) -> usize {
+fn adjacent_black_tiles_count(tiles: &TileMap, loc: &Hex) -> usize {
     let mut count = 0usize;
-    for d in &DIRS {
-        let adj = *loc + Delta::from(*d);
+    for adj in loc.neighbours() {
         if let Some(&Color::Black) = tiles.get(&adj) {
             count += 1;
         }
@@ -118,9 +61,7 @@ fn exhibit_next_day(tiles: &TileMap) -> TileMap {
     let mut next_day = TileMap::new();
 
     for c in tiles.keys() {
-        for d in &DIRS {
-            let loc = *c + Delta::from(*d);
-
+        for loc in c.neighbours() {
             if next_day.contains_key(&loc) {
                 continue;
             }
@@ -162,7 +103,7 @@ fn count_black_tiles(tiles: &TileMap) -> usize {
 
 fn main() {
     let input = get_input(24);
-    let tiles_coordinates: Vec<_> = parse_input(&input);
+    let tiles_coordinates = parse_input(&input).unwrap();
     let tiles = flip_tiles(&tiles_coordinates);
     dbg!(count_black_tiles(&tiles));
 
@@ -173,10 +114,13 @@ fn main() {
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc2020::fuzz::check_parser;
+    use aoc2020::generators::hex_paths;
+    use aoc2020::rng::Rng;
 
     fn get_test_tiles_map() -> TileMap {
         let input = include_str!("day24_test_directions.txt");
-        flip_tiles(&parse_input(input))
+        flip_tiles(&parse_input(input).unwrap())
     }
 
     #[test]
@@ -186,6 +130,23 @@ mod test {
         assert_eq!(count, 10);
     }
 
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(&[include_str!("day24_test_directions.txt")], parse_input);
+    }
+
+    #[test]
+    fn generated_paths() {
+        let mut rng = Rng::new(24);
+        for count in 1..100 {
+            let coords = parse_input(&hex_paths(&mut rng, count, 20)).unwrap();
+            let tiles = flip_tiles(&coords);
+            // Every flip changes the number of black tiles by exactly one
+            assert_eq!(count_black_tiles(&tiles) % 2, count % 2);
+            exhibit_nth_day(&tiles, 10);
+        }
+    }
+
     #[test]
     fn test_exhibit_day1() {
         let tiles = get_test_tiles_map();
@@ -0,0 +1,61 @@
+use aoc2020::aoc_input::get_input;
+
+const MODULO: u64 = 20201227;
+const E: u64 = 7;
+
+fn brute(pk: u64) -> u64 {
+    let mut r = 1u64;
+    let mut i = 0u64;
+    loop {
+        if r == pk {
+            break i;
+        }
+        r = (r * E) % MODULO;
+        i += 1;
+    }
+}
+
+fn modexp(num: u64, exp: u64) -> u64 {
+    let mut res = 1u64;
+    for _ in 0..exp {
+        res = (res * num) % MODULO;
+    }
+    res
+}
+
+fn parse_input(input: &str) -> Result<(u64, u64), &'static str> {
+    let nums = input
+        .lines()
+        .map(|line| line.parse().or(Err("Invalid public key")))
+        .collect::<Result<Vec<u64>, _>>()?;
+
+    // Keys outside 1..MODULO are never reached by brute()
+    if nums.iter().any(|&n| n == 0 || n >= MODULO) {
+        return Err("Public key out of range");
+    }
+    match nums[..] {
+        [pub1, pub2] => Ok((pub1, pub2)),
+        _ => Err("Expected exactly two public keys"),
+    }
+}
+
+fn main() {
+    let input = get_input(25);
+    let (pub1, pub2) = parse_input(&input).unwrap();
+
+    let pri1 = brute(pub1);
+    dbg!(pri1);
+    let enc = modexp(pub2, pri1);
+    dbg!(enc);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::fuzz::check_parser;
+
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(&["5764801\n17807724\n"], parse_input);
+    }
+}
@@ -0,0 +1,76 @@
+use aoc2020::aoc_input::get_input;
+use aoc2020::coordinates::{Coord, Delta};
+use aoc2020::grid::{Grid, Topology};
+use std::convert::TryFrom;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Square {
+    Open,
+    Tree,
+}
+
+impl TryFrom<char> for Square {
+    type Error = &'static str;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Square::Open),
+            '#' => Ok(Square::Tree),
+            _ => Err("Invalid char"),
+        }
+    }
+}
+
+// The map repeats to the right as far as needed
+fn parse_input(input: &str) -> Result<Grid<Square>, &'static str> {
+    let map: Grid<Square> = input.parse()?;
+    Ok(map.with_topology(Topology::WrapHorizontal))
+}
+
+const SLOPES: [Delta; 5] = [
+    Delta(1, 1),
+    Delta(3, 1),
+    Delta(5, 1),
+    Delta(7, 1),
+    Delta(1, 2),
+];
+
+fn count_trees(map: &Grid<Square>, slope: Delta) -> usize {
+    Coord::origin()
+        .ray(slope)
+        .map_while(|c| map.get(c))
+        .filter(|&&s| s == Square::Tree)
+        .count()
+}
+
+fn main() {
+    let input = get_input(3);
+    let map = parse_input(&input).unwrap();
+    let tree_counts: Vec<_> = SLOPES.iter().map(|&s| count_trees(&map, s)).collect();
+
+    dbg!(tree_counts[1]);
+    let product = tree_counts.iter().product::<usize>();
+    dbg!(product);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::fuzz::check_parser;
+
+    const EXAMPLE: &str = "..##.......\n#...#...#..\n.#....#..#.\n..#.#...#.#\n.#...##..#.\n\
+                           ..#.##.....\n.#.#.#....#\n.#........#\n#.##...#...\n#...##....#\n\
+                           .#..#...#.#\n";
+
+    #[test]
+    fn test_count_trees() {
+        let map = parse_input(EXAMPLE).unwrap();
+        let counts: Vec<_> = SLOPES.iter().map(|&s| count_trees(&map, s)).collect();
+        assert_eq!(counts, [2, 7, 3, 4, 2]);
+    }
+
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(&["..##.......\n#...#...#..\n.#....#..#.\n"], parse_input);
+    }
+}
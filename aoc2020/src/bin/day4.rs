@@ -126,7 +126,7 @@ impl Passport {
         ];
         // dbg!(results);
 
-        if results.iter().any(|r| *r == CheckResult::Invalid) {
+        if results.contains(&CheckResult::Invalid) {
             return CheckResult::Invalid;
         }
 
@@ -134,35 +134,38 @@ impl Passport {
             return CheckResult::Present;
         }
 
-        return CheckResult::PresentAndValid;
+        CheckResult::PresentAndValid
     }
 }
 
-fn parse_passport(lines: &[&str]) -> Passport {
+fn parse_passport(lines: &[&str]) -> Result<Passport, &'static str> {
     let mut fields = HashMap::<String, String>::new();
     for line in lines {
         for field in line.split(' ') {
             let mut kv = field.split(':');
-            let key = kv.next().expect("Malformed field key");
-            let value = kv.next().expect("Malformed field value");
-            if !kv.next().is_none() {
-                panic!("Malformed field structure");
+            let key = kv.next().ok_or("Malformed field key")?;
+            let value = kv.next().ok_or("Malformed field value")?;
+            if kv.next().is_some() {
+                return Err("Malformed field structure");
             }
 
             fields.insert(key.to_owned(), value.to_owned());
         }
     }
-    Passport { fields }
+    Ok(Passport { fields })
 }
 
-fn main() {
-    let input = get_input(4);
+fn parse_input(input: &str) -> Result<Vec<Passport>, &'static str> {
     let lines: Vec<_> = input.lines().collect();
-
-    let passports: Vec<_> = lines
+    lines
         .split(|line| line.is_empty())
         .map(parse_passport)
-        .collect();
+        .collect()
+}
+
+fn main() {
+    let input = get_input(4);
+    let passports = parse_input(&input).unwrap();
 
     let mut all_present_count = 0usize;
     let mut all_valid_count = 0usize;
@@ -176,3 +179,23 @@ fn main() {
     dbg!(all_present_count);
     dbg!(all_valid_count);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::fuzz::check_parser;
+
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(
+            &["ecl:gry pid:860033327 eyr:2020 hcl:#fffffd\n\
+             byr:1937 iyr:2017 cid:147 hgt:183cm\n\
+             \n\
+             hcl:#ae17e1 iyr:2013\n\
+             eyr:2024\n\
+             ecl:brn pid:760753108 byr:1931\n\
+             hgt:179cm\n"],
+            parse_input,
+        );
+    }
+}
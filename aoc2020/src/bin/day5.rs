@@ -18,11 +18,18 @@ impl FromStr for BoardingPass {
     type Err = &'static str;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (enc_row, enc_col) = s.split_at(7);
-        if enc_row.len() != 7 || enc_col.len() != 3 {
+        if s.len() != 10 || !s.is_ascii() {
             return Err("Invalid boarding pass string length");
         };
 
+        let (enc_row, enc_col) = s.split_at(7);
+        if !enc_row.chars().all(|c| c == 'F' || c == 'B') {
+            return Err("Invalid row");
+        }
+        if !enc_col.chars().all(|c| c == 'L' || c == 'R') {
+            return Err("Invalid column");
+        }
+
         let bin_row = enc_row.replace('F', "0").replace('B', "1");
         let bin_col = enc_col.replace('L', "0").replace('R', "1");
 
@@ -33,10 +40,13 @@ impl FromStr for BoardingPass {
     }
 }
 
+fn parse_input(input: &str) -> Result<HashSet<BoardingPass>, &'static str> {
+    input.lines().map(|line| line.parse()).collect()
+}
+
 fn main() {
     let input = get_input(5);
-    let boarding_passes: HashSet<BoardingPass> =
-        input.lines().map(|line| line.parse().unwrap()).collect();
+    let boarding_passes = parse_input(&input).unwrap();
 
     let max_seat_id = boarding_passes.iter().map(|bp| bp.seat_id()).max().unwrap();
     dbg!(max_seat_id);
@@ -55,3 +65,17 @@ fn main() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::fuzz::check_parser;
+
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(
+            &["FBFBBFFRLR\nBFFFBBFRRR\nFFFBBBFRRR\nBBFFBBFRLL\n"],
+            parse_input,
+        );
+    }
+}
@@ -1,8 +1,19 @@
 use aoc2020::aoc_input::get_input;
 
+fn parse_input(input: &str) -> Result<Vec<&str>, &'static str> {
+    let lines: Vec<_> = input.lines().collect();
+    if lines
+        .iter()
+        .any(|line| line.chars().any(|c| !c.is_ascii_lowercase()))
+    {
+        return Err("Invalid answer");
+    }
+    Ok(lines)
+}
+
 fn main() {
     let input = get_input(6);
-    let lines: Vec<_> = input.lines().collect();
+    let lines = parse_input(&input).unwrap();
 
     let mut any_total_count = 0usize;
     let mut all_total_count = 0usize;
@@ -30,3 +41,16 @@ fn main() {
     dbg!(any_total_count);
     dbg!(all_total_count);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::fuzz::check_parser;
+
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(&["abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb\n"], |s| {
+            parse_input(s).map(|v| v.len())
+        });
+    }
+}
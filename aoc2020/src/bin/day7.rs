@@ -0,0 +1,135 @@
+use aoc2020::aoc_input::get_input;
+use std::collections::{HashMap, HashSet};
+
+struct Rules {
+    contained_by: HashMap<String, Vec<String>>,
+    contains: HashMap<String, Vec<(usize, String)>>,
+}
+
+fn parse_contained_item(s: &str) -> Result<(usize, String), &'static str> {
+    let split_pos = s.find(" bag").ok_or("Invalid rule item")?;
+    let stripped = &s[..split_pos];
+    let num_sep_pos = stripped.find(' ').ok_or("Invalid rule item")?;
+
+    let num = &stripped[..num_sep_pos];
+    let num = num.parse::<usize>().or(Err("Invalid rule item number"))?;
+    let color = stripped[num_sep_pos + 1..].to_owned();
+
+    Ok((num, color))
+}
+
+fn parse_rules(input: &str) -> Result<Rules, &'static str> {
+    let mut rules = Rules {
+        contained_by: HashMap::new(),
+        contains: HashMap::new(),
+    };
+
+    for line in input.lines() {
+        let mut split = line.split(" bags contain ");
+        let container = split.next().ok_or("Invalid rule container")?;
+        let contained = split.next().ok_or("Invalid rule contained")?;
+
+        if split.next().is_some() {
+            return Err("Invalid rule split");
+        }
+
+        rules.contained_by.entry(container.to_owned()).or_default();
+
+        let contained = match contained {
+            "no other bags." => Vec::new(),
+            _ => contained
+                .split(", ")
+                .map(parse_contained_item)
+                .collect::<Result<_, _>>()?,
+        };
+
+        for (_, v) in contained.iter() {
+            let inv = rules.contained_by.entry(v.clone()).or_default();
+            inv.push(container.to_owned());
+        }
+
+        rules.contains.insert(container.to_owned(), contained);
+    }
+
+    if rules
+        .contained_by
+        .keys()
+        .any(|k| !rules.contains.contains_key(k))
+    {
+        return Err("Contained color has no rule");
+    }
+
+    Ok(rules)
+}
+
+fn dfs_containers(rules: &Rules, key: &str, containers: &mut HashSet<String>) {
+    for container in rules.contained_by.get(key).unwrap() {
+        containers.insert(container.clone());
+        dfs_containers(rules, container, containers);
+    }
+}
+
+fn dfs_contained_total(rules: &Rules, key: &str) -> usize {
+    let contained = rules.contains.get(key).unwrap();
+
+    let mut total = 1usize;
+    for (count, color) in contained {
+        total += count * dfs_contained_total(rules, color);
+    }
+    total
+}
+
+fn main() {
+    let input = get_input(7);
+    let rules = parse_rules(&input).unwrap();
+
+    let my_bag = "shiny gold";
+
+    let mut containers = HashSet::<String>::new();
+    dfs_containers(&rules, my_bag, &mut containers);
+    dbg!(containers.len());
+
+    dbg!(dfs_contained_total(&rules, my_bag) - 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::fuzz::check_parser;
+    use aoc2020::generators::bag_rules;
+    use aoc2020::rng::Rng;
+
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(
+            &[
+                "light red bags contain 1 bright white bag, 2 muted yellow bags.\n\
+             bright white bags contain 1 shiny gold bag.\n\
+             muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.\n\
+             shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.\n\
+             dark olive bags contain 3 faded blue bags, 4 dotted black bags.\n\
+             vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.\n\
+             faded blue bags contain no other bags.\n\
+             dotted black bags contain no other bags.\n",
+            ],
+            |s| parse_rules(s).map(|r| r.contains.len()),
+        );
+    }
+
+    #[test]
+    fn generated_rules() {
+        let mut rng = Rng::new(7);
+        for _ in 0..20 {
+            let input = bag_rules(&mut rng, 200, 4, 6);
+            let rules = parse_rules(&input).unwrap();
+            assert_eq!(rules.contains.len(), 200);
+
+            let mut containers = HashSet::new();
+            dfs_containers(&rules, "shiny gold", &mut containers);
+            assert!(!containers.contains("shiny gold"));
+            for container in &containers {
+                assert!(dfs_contained_total(&rules, container) > 1);
+            }
+        }
+    }
+}
@@ -120,26 +120,70 @@ impl Machine {
     }
 }
 
+fn parse_input(input: &str) -> Result<Vec<Insn>, &'static str> {
+    input.lines().map(|s| s.parse::<Insn>()).collect()
+}
+
+fn flipped_variants(code: &[Insn]) -> impl Iterator<Item = Vec<Insn>> + '_ {
+    code.iter().enumerate().filter_map(move |(i, insn)| {
+        let mut modified = code.to_vec();
+        modified[i] = insn.flip()?;
+        Some(modified)
+    })
+}
+
+fn repair(code: &[Insn]) -> Option<isize> {
+    flipped_variants(code).find_map(|modified| match Machine::new(modified).run_once() {
+        RunResult::InfiniteLoop(_) => None,
+        RunResult::Termination(acc) => Some(acc),
+    })
+}
+
 fn main() {
     let input = get_input(8);
-    let code: Vec<_> = input.lines().map(|s| s.parse::<Insn>().unwrap()).collect();
+    let code = parse_input(&input).unwrap();
 
     let mut machine = Machine::new(code.clone());
     dbg!(machine.run_once());
 
-    for i in 0..code.len() {
-        let flipped = match code[i].flip() {
-            None => continue,
-            Some(insn) => insn,
-        };
-        let mut modified = code.clone();
-        modified[i] = flipped;
-        match Machine::new(modified).run_once() {
-            RunResult::InfiniteLoop(_) => continue,
-            RunResult::Termination(acc) => {
-                println!("Termination accumulator: {}", acc);
-                break;
-            }
+    if let Some(acc) = repair(&code) {
+        println!("Termination accumulator: {}", acc);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::fuzz::check_parser;
+    use aoc2020::generators::handheld_program;
+    use aoc2020::rng::Rng;
+
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(
+            &["nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n"],
+            parse_input,
+        );
+    }
+
+    #[test]
+    fn generated_programs() {
+        let mut rng = Rng::new(8);
+        for len in 2..200 {
+            let code = parse_input(&handheld_program(&mut rng, len)).unwrap();
+            let result = Machine::new(code.clone()).run_once();
+            assert!(matches!(result, RunResult::InfiniteLoop(_)));
+
+            let fixes = flipped_variants(&code)
+                .filter(|m| {
+                    matches!(
+                        Machine::new(m.clone()).run_once(),
+                        RunResult::Termination(_)
+                    )
+                })
+                .count();
+            assert_eq!(fixes, 1);
+            assert!(repair(&code).is_some());
         }
     }
 }
@@ -1,8 +1,15 @@
 use aoc2020::aoc_input::get_input;
 
+fn parse_input(input: &str) -> Result<Vec<usize>, &'static str> {
+    input
+        .lines()
+        .map(|line| line.parse().or(Err("Invalid number")))
+        .collect()
+}
+
 fn main() {
     let input = get_input(9);
-    let nums: Vec<usize> = input.lines().map(|line| line.parse().unwrap()).collect();
+    let nums = parse_input(&input).unwrap();
     dbg!(nums.len());
 
     const WINDOW: usize = 25;
@@ -37,8 +44,7 @@ fn main() {
         let mut min = ni;
         let mut max = ni;
 
-        for j in i + 1..invalid_num_idx {
-            let nj = nums[j];
+        for &nj in &nums[i + 1..invalid_num_idx] {
             sum += nj;
             min = min.min(nj);
             max = max.max(nj);
@@ -52,3 +58,17 @@ fn main() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::fuzz::check_parser;
+
+    #[test]
+    fn fuzz_parse_input() {
+        check_parser(
+            &["35\n20\n15\n25\n47\n40\n62\n55\n65\n95\n102\n117\n150\n182\n"],
+            parse_input,
+        );
+    }
+}
@@ -0,0 +1,257 @@
+use aoc::grid::{Axis, Grid, Rotation};
+use aoc::rng::Rng;
+use std::collections::HashSet;
+
+const ADJECTIVES: &[&str] = &[
+    "bright", "clear", "dark", "dim", "dotted", "drab", "dull", "faded", "light", "mirrored",
+    "muted", "pale", "plaid", "posh", "shiny", "striped", "vibrant", "wavy",
+];
+
+const COLORS: &[&str] = &[
+    "aqua", "beige", "black", "blue", "bronze", "brown", "coral", "crimson", "cyan", "gold",
+    "gray", "green", "indigo", "lime", "magenta", "olive", "orange", "plum", "red", "salmon",
+    "tan", "teal", "tomato", "violet", "white", "yellow",
+];
+
+// Day 7: `count` bag rules (at most ADJECTIVES.len() * COLORS.len()) forming
+// a DAG at most `max_depth` levels deep, always including "shiny gold", with
+// up to `max_children` contained colors per rule.
+pub fn bag_rules(rng: &mut Rng, count: usize, max_children: usize, max_depth: usize) -> String {
+    assert!(count <= ADJECTIVES.len() * COLORS.len());
+
+    let mut names = vec!["shiny gold".to_string()];
+    let mut seen: HashSet<String> = names.iter().cloned().collect();
+    while names.len() < count {
+        let name = format!("{} {}", rng.choose(ADJECTIVES), rng.choose(COLORS));
+        if seen.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+    // Bags only contain bags of deeper levels, which keeps the graph acyclic
+    // and the total bag counts from overflowing.
+    let levels: Vec<usize> = names.iter().map(|_| rng.below(max_depth)).collect();
+
+    let mut lines = Vec::with_capacity(count);
+    for (i, name) in names.iter().enumerate() {
+        let mut later: Vec<_> = (0..count)
+            .filter(|&j| levels[j] > levels[i])
+            .map(|j| &names[j])
+            .collect();
+        rng.shuffle(&mut later);
+        let children = rng.below(max_children.min(later.len()) + 1);
+
+        let contained = if children == 0 {
+            "no other bags".to_string()
+        } else {
+            let items: Vec<_> = later[..children]
+                .iter()
+                .map(|color| {
+                    let n = 1 + rng.below(5);
+                    let bags = if n == 1 { "bag" } else { "bags" };
+                    format!("{} {} {}", n, color, bags)
+                })
+                .collect();
+            items.join(", ")
+        };
+        lines.push(format!("{} bags contain {}.", name, contained));
+    }
+
+    rng.shuffle(&mut lines);
+    lines.join("\n") + "\n"
+}
+
+// Day 8: a program of `len` instructions which loops forever, and which
+// terminates when exactly one specific jmp is changed into a nop.
+//
+// The program runs straight through a prefix ending in a backwards jmp, and
+// the suffix after it is only reachable once that jmp is fixed. Flipping any
+// executed nop jumps backwards (or in place), flipping a forward jmp falls
+// into a skipped slot which jumps back to the start, and flipping anything
+// in the unreachable suffix has no effect, so all other fixes still loop.
+pub fn handheld_program(rng: &mut Rng, len: usize) -> String {
+    assert!(len >= 2);
+    let fix_at = 1 + rng.below(len - 1);
+    let mut code: Vec<Option<(&str, isize)>> = vec![None; len];
+
+    let mut ip = 0usize;
+    while ip < len {
+        if ip == fix_at {
+            let back = rng.below(fix_at + 1) as isize;
+            code[ip] = Some(("jmp", -back));
+            ip += 1;
+            continue;
+        }
+
+        let limit = if ip < fix_at { fix_at } else { len };
+        match rng.below(4) {
+            0 if ip + 2 <= limit => {
+                let skip = 2 + rng.below(3.min(limit - ip - 1));
+                code[ip] = Some(("jmp", skip as isize));
+                ip += skip;
+            }
+            1 if ip < fix_at => {
+                let back = rng.below(ip + 1) as isize;
+                code[ip] = Some(("nop", -back));
+                ip += 1;
+            }
+            1 => {
+                code[ip] = Some(("nop", rng.range(-(ip as isize)..1)));
+                ip += 1;
+            }
+            _ => {
+                code[ip] = Some(("acc", rng.range(-99..100)));
+                ip += 1;
+            }
+        }
+    }
+
+    let lines: Vec<_> = code
+        .iter()
+        .enumerate()
+        .map(|(i, insn)| {
+            let (op, opnd) = insn.unwrap_or(("jmp", -(i as isize)));
+            format!("{} {:+}", op, opnd)
+        })
+        .collect();
+    lines.join("\n") + "\n"
+}
+
+#[derive(Debug, Clone)]
+pub struct Jigsaw {
+    pub input: String,
+    pub image: Grid<char>,
+    pub corner_ids: [usize; 4],
+}
+
+fn random_row(rng: &mut Rng, len: usize) -> String {
+    (0..len)
+        .map(|_| if rng.chance(1, 2) { '#' } else { '.' })
+        .collect()
+}
+
+fn unique_edge(
+    rng: &mut Rng,
+    first: char,
+    last: char,
+    len: usize,
+    used: &mut HashSet<String>,
+) -> String {
+    loop {
+        let edge = format!("{}{}{}", first, random_row(rng, len - 2), last);
+        let rev: String = edge.chars().rev().collect();
+        if edge == rev || used.contains(&edge) || used.contains(&rev) {
+            continue;
+        }
+        used.insert(edge.clone());
+        return edge;
+    }
+}
+
+// Day 20: a `side` x `side` arrangement of square tiles of `tile_size`
+// characters, each randomly rotated and flipped, plus the image formed by
+// their interiors in the original orientation. Every edge is unique and not
+// a palindrome, so each tile fits in exactly one place.
+pub fn jigsaw_tiles(rng: &mut Rng, side: usize, tile_size: usize) -> Jigsaw {
+    assert!(side >= 1 && tile_size >= 6);
+
+    let corners: Vec<Vec<char>> = (0..=side)
+        .map(|_| random_row(rng, side + 1).chars().collect())
+        .collect();
+
+    let mut used = HashSet::new();
+    let mut horizontal = vec![Vec::with_capacity(side); side + 1];
+    let mut vertical = vec![Vec::with_capacity(side + 1); side];
+    for (i, row) in horizontal.iter_mut().enumerate() {
+        for j in 0..side {
+            let (a, b) = (corners[i][j], corners[i][j + 1]);
+            row.push(unique_edge(rng, a, b, tile_size, &mut used));
+        }
+    }
+    for (i, row) in vertical.iter_mut().enumerate() {
+        for (&a, &b) in corners[i].iter().zip(&corners[i + 1]) {
+            row.push(unique_edge(rng, a, b, tile_size, &mut used));
+        }
+    }
+
+    let mut ids: Vec<usize> = (1000..10000).collect();
+    rng.shuffle(&mut ids);
+
+    let inner = tile_size - 2;
+    let mut image_rows = vec![String::new(); side * inner];
+    let mut tiles = Vec::with_capacity(side * side);
+    for i in 0..side {
+        for j in 0..side {
+            let top: Vec<char> = horizontal[i][j].chars().collect();
+            let bottom: Vec<char> = horizontal[i + 1][j].chars().collect();
+            let left: Vec<char> = vertical[i][j].chars().collect();
+            let right: Vec<char> = vertical[i][j + 1].chars().collect();
+
+            let mut s = String::with_capacity(tile_size * (tile_size + 1));
+            s.extend(&top);
+            s.push('\n');
+            for y in 1..tile_size - 1 {
+                let interior = random_row(rng, inner);
+                image_rows[i * inner + y - 1].push_str(&interior);
+                s.push(left[y]);
+                s.push_str(&interior);
+                s.push(right[y]);
+                s.push('\n');
+            }
+            s.extend(&bottom);
+
+            let mut tile: Grid<char> = s.parse().unwrap();
+            let rotation = *rng.choose(&[
+                Rotation::Cw0,
+                Rotation::Cw90,
+                Rotation::Cw180,
+                Rotation::Cw270,
+            ]);
+            tile.rotate_inplace(rotation);
+            if rng.chance(1, 2) {
+                tile.flip_inplace(Axis::Horizontal);
+            }
+            tiles.push((ids[i * side + j], tile));
+        }
+    }
+
+    let corner_ids = [
+        tiles[0].0,
+        tiles[side - 1].0,
+        tiles[side * (side - 1)].0,
+        tiles[side * side - 1].0,
+    ];
+
+    rng.shuffle(&mut tiles);
+    let blocks: Vec<_> = tiles
+        .iter()
+        .map(|(id, tile)| format!("Tile {}:\n{}", id, tile))
+        .collect();
+
+    Jigsaw {
+        input: blocks.join("\n"),
+        image: image_rows.join("\n").parse().unwrap(),
+        corner_ids,
+    }
+}
+
+const HEX_DIRS: &[&str] = &["e", "se", "sw", "w", "nw", "ne"];
+
+// Day 24: `count` lines of up to `max_len` hex directions. Some lines are
+// shuffled copies of earlier ones, so that tiles get flipped more than once.
+pub fn hex_paths(rng: &mut Rng, count: usize, max_len: usize) -> String {
+    let mut paths: Vec<Vec<&str>> = Vec::with_capacity(count);
+    for _ in 0..count {
+        let path = if !paths.is_empty() && rng.chance(1, 4) {
+            let mut path = rng.choose(&paths).clone();
+            rng.shuffle(&mut path);
+            path
+        } else {
+            let len = 1 + rng.below(max_len);
+            (0..len).map(|_| *rng.choose(HEX_DIRS)).collect()
+        };
+        paths.push(path);
+    }
+
+    let lines: Vec<_> = paths.iter().map(|p| p.concat()).collect();
+    lines.join("\n") + "\n"
+}
@@ -0,0 +1,4 @@
+pub use aoc::{coordinates, fuzz, grid, rng, symmetry};
+
+pub mod aoc_input;
+pub mod generators;
@@ -1,3 +0,0 @@
-pub mod aoc_input;
-pub mod coordinates;
-pub mod grid;